@@ -0,0 +1,123 @@
+use std::io::{self, Write};
+
+use fivewords::Solution;
+
+const MAGIC: &[u8; 4] = b"FWB1";
+
+// --format binary: a small header, then one fixed-size record per
+// solution, for programs that would rather not parse text. Integers
+// are little-endian u32s:
+//
+//   header   "FWB1", word length, set size
+//   record   each word's letters as a mask (bit 0 = 'a'), in the order
+//            the search picked them, then one byte: the unused letter
+//            in ASCII, or 0 if there's none
+//
+// so a standard puzzle's record is 21 bytes, and every record in a
+// stream is the same size, and a consumer can mmap the stream and step
+// through it. A solution that leaves several letters unused records
+// the first of them (as --per-letter-limit counts it). A mask stands
+// for the dictionary's first spelling of its letters, the one the
+// search keeps: --decode-binary reads a stream back into words against
+// the dictionary it was made from.
+//
+pub struct Writer<W: Write> {
+    out: io::BufWriter<W>,
+    record: Vec<u8>,
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(out: W, word_len: usize, set_size: usize) -> io::Result<Self> {
+        let mut out = io::BufWriter::new(out);
+
+        out.write_all(MAGIC)?;
+        out.write_all(&(word_len as u32).to_le_bytes())?;
+        out.write_all(&(set_size as u32).to_le_bytes())?;
+
+        Ok(Writer {
+            out,
+            record: Vec::with_capacity(record_len(set_size)),
+        })
+    }
+
+    pub fn write(&mut self, solution: &Solution) -> io::Result<()> {
+        self.record.clear();
+
+        for word in solution.words() {
+            let mask = word.iter().fold(0u32, |mask, z| mask | 1 << z.ord());
+
+            self.record.extend(mask.to_le_bytes());
+        }
+
+        self.record.push(match solution.unused() {
+            [] => 0,
+            [first, ..] => first.chr() as u8,
+        });
+        self.out.write_all(&self.record)
+    }
+
+    // push out what's been written so far (for --stream-immediate, which
+    // wants each record as soon as it's found)
+    //
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+pub fn record_len(set_size: usize) -> usize {
+    4 * set_size + 1
+}
+
+// a solution read back from a binary stream
+//
+pub struct Record {
+    pub masks: Vec<u32>,
+    pub unused: Option<char>,
+}
+
+// the header's word length, and the records after it
+//
+pub fn read(bytes: &[u8]) -> Result<(usize, Vec<Record>), String> {
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+
+    if bytes.len() < 12 || &bytes[..4] != MAGIC {
+        return Err("not a fivewords binary stream".to_string());
+    }
+
+    let (word_len, set_size) = (u32_at(4) as usize, u32_at(8) as usize);
+    let size = record_len(set_size);
+    let body = &bytes[12..];
+
+    if word_len == 0 || set_size == 0 || !body.len().is_multiple_of(size) {
+        return Err("truncated or corrupt binary stream".to_string());
+    }
+
+    let mut records = Vec::with_capacity(body.len() / size);
+
+    for record in body.chunks_exact(size) {
+        let masks: Vec<u32> = record[..4 * set_size]
+            .chunks_exact(4)
+            .map(|mask| u32::from_le_bytes(mask.try_into().unwrap()))
+            .collect();
+        let unused = match record[4 * set_size] {
+            0 => None,
+            letter @ b'a'..=b'z' => Some(letter as char),
+            _ => return Err("corrupt record in binary stream".to_string()),
+        };
+
+        if masks
+            .iter()
+            .any(|&mask| mask >> 26 != 0 || mask.count_ones() as usize != word_len)
+        {
+            return Err("corrupt record in binary stream".to_string());
+        }
+
+        records.push(Record { masks, unused });
+    }
+
+    Ok((word_len, records))
+}
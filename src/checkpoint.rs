@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use fivewords::{Seed, ZChar};
+
+const MAGIC: &str = "fivewords checkpoint 1";
+
+// the state of an interruptible run: which seeds have been searched to
+// the end, and the output lines each of them produced, so a resumed
+// run can replay those lines and only search the unfinished seeds.
+//
+// The file is plain text:
+//
+//   fivewords checkpoint 1
+//   <header: dictionary hash and search parameters>
+//   seed q
+//     <output line>
+//     ...
+//
+// where a seed is written as its letter, or '-' for the single seed of
+// a search with nothing to skip.
+//
+pub struct Checkpoint {
+    path: PathBuf,
+    header: String,
+    done: Vec<(Seed, Vec<String>)>,
+
+    // lines found by seeds that are still being searched
+    //
+    pending: HashMap<Seed, Vec<String>>,
+
+    // how often it's written out, how many finished seeds it hasn't
+    // been written out with yet, and when it last was
+    //
+    interval: Interval,
+    unsaved: usize,
+    saved: Instant,
+}
+
+// --checkpoint-interval: write the checkpoint out after every so many
+// finished seeds, or once a seed finishes this long after it was last
+// written (and always at the end of the run). A crash loses the seeds
+// finished since, at most an interval's worth; the default is to write
+// it out after every seed.
+//
+#[derive(Clone, Copy, Debug)]
+pub enum Interval {
+    Seeds(usize),
+    Time(Duration),
+}
+
+impl Checkpoint {
+    // load the checkpoint at `path`, or start a fresh one if there
+    // isn't one yet. A checkpoint written for a different dictionary
+    // or different parameters is refused rather than mixed in.
+    //
+    pub fn open(path: &str, header: String) -> Result<Self, String> {
+        let mut checkpoint = Checkpoint {
+            path: PathBuf::from(path),
+            header,
+            done: Vec::new(),
+            pending: HashMap::new(),
+            interval: Interval::Seeds(1),
+            unsaved: 0,
+            saved: Instant::now(),
+        };
+
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(checkpoint),
+            Err(err) => return Err(format!("can't read checkpoint '{}': {}", path, err)),
+        };
+
+        let mut lines = text.lines();
+
+        if lines.next() != Some(MAGIC) {
+            return Err(format!("'{}' is not a checkpoint file", path));
+        }
+        if lines.next() != Some(checkpoint.header.as_str()) {
+            return Err(format!(
+                "checkpoint '{}' was written for a different dictionary or parameters",
+                path
+            ));
+        }
+
+        for line in lines {
+            if let Some(seed) = line.strip_prefix("seed ") {
+                let seed = parse_seed(seed)
+                    .ok_or_else(|| format!("bad seed '{}' in checkpoint '{}'", seed, path))?;
+
+                checkpoint.done.push((seed, Vec::new()));
+            } else if let (Some(found), Some((_, lines))) =
+                (line.strip_prefix("  "), checkpoint.done.last_mut())
+            {
+                lines.push(found.to_string());
+            } else {
+                return Err(format!("bad line '{}' in checkpoint '{}'", line, path));
+            }
+        }
+
+        Ok(checkpoint)
+    }
+
+    pub fn save_every(&mut self, interval: Interval) {
+        self.interval = interval;
+    }
+
+    pub fn is_done(&self, seed: Seed) -> bool {
+        self.done.iter().any(|(done, _)| *done == seed)
+    }
+
+    // the output of every finished seed
+    //
+    pub fn lines(&self) -> impl Iterator<Item = &String> {
+        self.done.iter().flat_map(|(_, lines)| lines)
+    }
+
+    pub fn record(&mut self, seed: Seed, line: String) {
+        self.pending.entry(seed).or_default().push(line);
+    }
+
+    // mark a seed finished, and write the checkpoint out if it's due
+    //
+    pub fn finish(&mut self, seed: Seed) -> io::Result<()> {
+        let lines = self.pending.remove(&seed).unwrap_or_default();
+
+        self.done.push((seed, lines));
+        self.unsaved += 1;
+
+        let due = match self.interval {
+            Interval::Seeds(seeds) => self.unsaved >= seeds,
+            Interval::Time(every) => self.saved.elapsed() >= every,
+        };
+
+        match due {
+            true => self.save(),
+            false => Ok(()),
+        }
+    }
+
+    // write out any finished seeds it hasn't been yet, at the end
+    //
+    pub fn flush(&mut self) -> io::Result<()> {
+        match self.unsaved {
+            0 => Ok(()),
+            _ => self.save(),
+        }
+    }
+
+    fn save(&mut self) -> io::Result<()> {
+        self.unsaved = 0;
+        self.saved = Instant::now();
+
+        let mut text = format!("{}\n{}\n", MAGIC, self.header);
+
+        for (seed, lines) in &self.done {
+            match seed {
+                Some(letter) => text.push_str(&format!("seed {}\n", letter)),
+                None => text.push_str("seed -\n"),
+            }
+
+            // (an entry of several lines, like a --grid square, is
+            // kept a line at a time, to be replayed just the same)
+            //
+            for line in lines.iter().flat_map(|line| line.split('\n')) {
+                text.push_str("  ");
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+
+        // write it alongside and rename it over the old one, so being
+        // killed mid-write never leaves a truncated checkpoint behind
+        //
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+
+        fs::write(&tmp, text)?;
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+fn parse_seed(seed: &str) -> Option<Seed> {
+    if seed == "-" {
+        return Some(None);
+    }
+
+    let mut chars = seed.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => ZChar::try_from(c).ok().map(Some),
+        _ => None,
+    }
+}
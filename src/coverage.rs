@@ -0,0 +1,246 @@
+use alloc::vec::Vec;
+
+use crate::mask::Mask;
+use crate::search::{Search, Tally};
+use crate::solution::Solution;
+use crate::solver::WHOLE;
+use crate::word::MAX_SYMBOLS;
+
+// the search for sets whose words may share letters, as long as they
+// cover at least `min` letters between them (--min-unique), and at
+// most `max`
+//
+pub(crate) struct Cover<M: Mask> {
+    // every candidate containing each (remapped) bit, with its place in
+    // the buckets, which orders the candidates
+    //
+    containing: Vec<Vec<(u32, M)>>,
+    min: usize,
+    max: usize,
+    max_len: usize,
+
+    // whether the words may share so many letters that one of them
+    // adds none at all
+    //
+    extras: bool,
+}
+
+impl<M: Mask> Cover<M> {
+    pub fn new(
+        ctx: &Search<M>,
+        unique: (usize, usize),
+        lengths: (usize, usize),
+        set_size: usize,
+    ) -> Self {
+        let (min, max) = unique;
+        let (min_len, max_len) = lengths;
+
+        let mut containing: Vec<Vec<(u32, M)>> =
+            (0..ctx.index.symbols).map(|_| Vec::new()).collect();
+
+        for (place, &bits) in ctx.index.lbit_lut.iter().enumerate() {
+            let mut rest = bits;
+
+            while rest != M::ZERO {
+                containing[rest.trailing_zeros() as usize].push((place as u32, bits));
+                rest = rest.clear_lowest();
+            }
+        }
+
+        Cover {
+            containing,
+            min,
+            max,
+            max_len,
+            extras: max_len * set_size >= min + min_len,
+        }
+    }
+
+    // like the disjoint search, fill the lowest letter still free, with
+    // any word containing it (sharing letters with the words before it,
+    // or not), or leave it unused while we have `skips` to spare.
+    // `blocked` holds the letters no word may use (those outside the
+    // alphabet, and those left unused), `filled` the letters that were
+    // the lowest free one when a word was placed for them, and `owner`
+    // the place of that word.
+    //
+    // A set could be reached once for each of its words containing the
+    // letter being filled, so that has to be the first (by place) of
+    // them: a later word may share a filled letter only when it comes
+    // after the word placed for it.
+    //
+    // Nor is every word placed for a letter it fills: one whose letters
+    // the others all cover already is an extra, added once the others
+    // are placed (straight after the last of them, so `skipped` says
+    // whether we've just left a letter unused instead).
+    //
+    #[allow(clippy::too_many_arguments)]
+    pub fn search(
+        &self,
+        ctx: &Search<M>,
+        selected: &mut [M],
+        tally: &mut Tally,
+        covered: M,
+        blocked: M,
+        filled: M,
+        owner: &mut [u32; MAX_SYMBOLS],
+        depth: usize,
+        skips: usize,
+        skipped: bool,
+    ) {
+        tally.visits += 1;
+        ctx.check_clock(tally.visits);
+
+        if ctx.stopped() {
+            return;
+        }
+
+        if depth == selected.len() {
+            if covered.count_ones() as usize >= self.min {
+                (ctx.emit)(WHOLE, selected, &mut tally.found);
+            }
+            return;
+        }
+
+        if self.extras && !skipped && covered.count_ones() as usize >= self.min {
+            self.close(
+                ctx,
+                selected,
+                &mut tally.found,
+                covered,
+                blocked,
+                filled,
+                owner,
+                depth,
+            );
+        }
+
+        let lowbit = (covered | blocked).trailing_ones();
+
+        // (the words still to place add at most their length each)
+        //
+        if lowbit >= ctx.index.symbols
+            || (covered.count_ones() as usize) + (selected.len() - depth) * self.max_len < self.min
+        {
+            return;
+        }
+
+        for &(place, bits) in &self.containing[lowbit as usize] {
+            // (a set only ever covers more letters as it grows)
+            //
+            if bits & blocked != M::ZERO || (covered | bits).count_ones() as usize > self.max {
+                continue;
+            }
+
+            let mut shared = bits & filled;
+            let mut first = true;
+
+            while shared != M::ZERO {
+                if owner[shared.trailing_zeros() as usize] > place {
+                    first = false;
+                    break;
+                }
+                shared = shared.clear_lowest();
+            }
+
+            if !first {
+                continue;
+            }
+
+            owner[lowbit as usize] = place;
+            selected[depth] = bits;
+            self.search(
+                ctx,
+                selected,
+                tally,
+                covered | bits,
+                blocked,
+                filled | M::bit(lowbit),
+                owner,
+                depth + 1,
+                skips,
+                false,
+            );
+        }
+
+        let lowmask = M::bit(lowbit);
+
+        if skips > 0 && ctx.required & lowmask == M::ZERO {
+            self.search(
+                ctx,
+                selected,
+                tally,
+                covered,
+                blocked | lowmask,
+                filled,
+                owner,
+                depth,
+                skips - 1,
+                true,
+            );
+        }
+    }
+
+    // fill the rest of the set with extras, each a word all of whose
+    // letters are covered (and that comes after the words placed for
+    // any it shares), in the order of their places
+    //
+    #[allow(clippy::too_many_arguments)]
+    fn close(
+        &self,
+        ctx: &Search<M>,
+        selected: &mut [M],
+        found: &mut Vec<Solution>,
+        covered: M,
+        blocked: M,
+        filled: M,
+        owner: &[u32; MAX_SYMBOLS],
+        depth: usize,
+    ) {
+        let extras: Vec<M> = ctx
+            .index
+            .lbit_lut
+            .iter()
+            .enumerate()
+            .filter(|&(place, &bits)| {
+                let mut shared = bits & filled;
+
+                while shared != M::ZERO {
+                    if owner[shared.trailing_zeros() as usize] > place as u32 {
+                        return false;
+                    }
+                    shared = shared.clear_lowest();
+                }
+
+                bits & !covered == M::ZERO
+                    && bits & blocked == M::ZERO
+                    && !selected[..depth].contains(&bits)
+            })
+            .map(|(_, &bits)| bits)
+            .collect();
+
+        fn pick<M: Mask>(
+            ctx: &Search<M>,
+            extras: &[M],
+            selected: &mut [M],
+            found: &mut Vec<Solution>,
+            depth: usize,
+        ) {
+            if ctx.stopped() {
+                return;
+            }
+
+            if depth == selected.len() {
+                (ctx.emit)(WHOLE, selected, found);
+                return;
+            }
+
+            for (i, &bits) in extras.iter().enumerate() {
+                selected[depth] = bits;
+                pick(ctx, &extras[i + 1..], selected, found, depth + 1);
+            }
+        }
+
+        pick(ctx, &extras, selected, found, depth);
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use fivewords::PreparedDict;
+
+// what --dict-info finds in a dictionary: how its lines are laid out,
+// how many of them the solver will have to skip, and why.
+//
+#[derive(Default)]
+struct Findings<'a> {
+    lines: usize,
+    bom: bool,
+    crlf: usize,
+    bare_cr: usize,
+    blank: usize,
+
+    // the first offending line of each kind, to show as an example
+    //
+    non_ascii: (usize, Option<&'a str>),
+    non_letter: (usize, Option<&'a str>),
+    uppercase: (usize, Option<&'a str>),
+
+    // line lengths (in characters), and the same for just the words
+    // the solver can use: plain letters, none repeated
+    //
+    lengths: BTreeMap<usize, usize>,
+    usable: BTreeMap<usize, usize>,
+}
+
+fn note<'a>(count: &mut (usize, Option<&'a str>), line: &'a str) {
+    count.0 += 1;
+    count.1.get_or_insert(line);
+}
+
+pub fn report(path: &str, text: &str, word_len: usize) {
+    let mut found = Findings {
+        bom: text.starts_with('\u{feff}'),
+        ..Default::default()
+    };
+
+    for line in text.split('\n') {
+        found.crlf += line.ends_with('\r') as usize;
+        found.bare_cr += line.trim_end_matches('\r').contains('\r') as usize;
+    }
+
+    for line in text.lines() {
+        found.lines += 1;
+
+        // the solver ignores whitespace around a word, so we do too
+        //
+        let line = line.trim();
+
+        if line.is_empty() {
+            found.blank += 1;
+            continue;
+        }
+
+        if !line.is_ascii() {
+            note(&mut found.non_ascii, line);
+        } else if !line.bytes().all(|b| b.is_ascii_alphabetic()) {
+            note(&mut found.non_letter, line);
+        }
+        if line.chars().any(char::is_uppercase) {
+            note(&mut found.uppercase, line);
+        }
+
+        *found.lengths.entry(line.chars().count()).or_default() += 1;
+    }
+
+    for word in PreparedDict::new(text).words() {
+        *found.usable.entry(word.len()).or_default() += 1;
+    }
+
+    println!("dictionary: {}", path);
+    println!("  lines:            {}", found.lines);
+    println!("  blank lines:      {}", found.blank);
+    println!(
+        "  byte order mark:  {}",
+        if found.bom { "yes" } else { "no" }
+    );
+    println!(
+        "  line endings:     {}",
+        match (found.crlf, found.bare_cr) {
+            (0, 0) => "LF",
+            (_, 0) => "CRLF",
+            _ => "CR",
+        }
+    );
+    println!("  non-ASCII lines:  {}", Example(found.non_ascii));
+    println!("  non-letter lines: {}", Example(found.non_letter));
+    println!("  uppercase words:  {}", Example(found.uppercase));
+    println!();
+    println!("  {:>6} {:>10} {:>10}", "length", "lines", "usable");
+
+    for (len, count) in &found.lengths {
+        let usable = found.usable.get(len).copied().unwrap_or(0);
+
+        println!("  {:>6} {:>10} {:>10}", len, count, usable);
+    }
+
+    warnings(&found, word_len);
+}
+
+// the usual reasons a dictionary gives fewer solutions than expected
+//
+fn warnings(found: &Findings, word_len: usize) {
+    let mut warnings = Vec::new();
+
+    if found.bom {
+        warnings.push(
+            "the file starts with a byte order mark, so its first word is skipped".to_string(),
+        );
+    }
+    if found.bare_cr > 0 {
+        warnings.push(
+            "lines end in a bare CR (old Mac style), so words run together into long lines"
+                .to_string(),
+        );
+    } else if found.crlf > 0 {
+        warnings
+            .push("lines end in CRLF; these are handled, but other tools may not be".to_string());
+    }
+    if found.non_ascii.0 > 0 {
+        let hint = if cfg!(feature = "unicode") {
+            " (try --normalize strip)"
+        } else {
+            ""
+        };
+
+        warnings.push(format!(
+            "{} lines contain non-ASCII characters and are skipped{}",
+            found.non_ascii.0, hint
+        ));
+    }
+    if found.non_letter.0 > 0 {
+        warnings.push(format!(
+            "{} lines contain digits, punctuation or spaces and are skipped",
+            found.non_letter.0
+        ));
+    }
+    if found.uppercase.0 > 0 {
+        warnings.push(format!(
+            "{} words contain uppercase letters; case is ignored, so proper nouns and acronyms count as words",
+            found.uppercase.0
+        ));
+    }
+    if found.usable.get(&word_len).copied().unwrap_or(0) == 0 {
+        warnings.push(format!("no usable words of length {}", word_len));
+    }
+
+    if !warnings.is_empty() {
+        println!();
+    }
+    for warning in warnings {
+        println!("warning: {}", warning);
+    }
+}
+
+// a count, with the first line it counted as an example
+//
+struct Example<'a>((usize, Option<&'a str>));
+
+impl Display for Example<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            (count, Some(line)) => write!(f, "{} (e.g. {:?})", count, line),
+            (count, None) => write!(f, "{}", count),
+        }
+    }
+}
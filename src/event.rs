@@ -0,0 +1,32 @@
+use alloc::vec::Vec;
+
+use crate::word::{ZChar, ZWord};
+
+/// A step of the search, as handed to an
+/// [`event_log`](crate::SolverBuilder::event_log). `depth` is how many
+/// words the branch has placed so far.
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A branch starts, leaving `seed` (the first letter it skips)
+    /// unused, or skipping none when the words cover every letter.
+    Seed { seed: Option<ZChar> },
+    /// The branch fills `letter`, the lowest (rarest) letter it has yet
+    /// to cover, with each of the `words` candidates that contain it in
+    /// turn.
+    Enter {
+        depth: usize,
+        letter: ZChar,
+        words: usize,
+    },
+    /// It places `word`, which shares no letters with those already
+    /// placed.
+    Try { depth: usize, word: ZWord },
+    /// It leaves `letter` unused instead, while it has spare letters.
+    Skip { depth: usize, letter: ZChar },
+    /// It gives up: fewer of the letters left have any candidates than
+    /// there are words still to place.
+    Prune { depth: usize },
+    /// It has placed every word, in this order. The solution still has
+    /// to pass any required letters and filter.
+    Solution { words: Vec<ZWord> },
+}
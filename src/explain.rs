@@ -0,0 +1,173 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::prepared::{read_letters, PreparedDict, Problem};
+use crate::solver::{Anagram, Config};
+use crate::word::{ZChar, ZWord};
+
+/// Why a word does, or doesn't, appear in a [`Solver`](crate::Solver)'s
+/// solutions: the first of the solver's checks the word fails, or how
+/// many solutions it's in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Explanation {
+    /// It contains something other than a plain (ASCII) letter.
+    NotALetter(char),
+    /// It uses this letter more than once.
+    RepeatedLetter(char),
+    /// It has this many letters, rather than the solver's word length.
+    WrongLength(usize),
+    /// It uses a letter that isn't allowed, is excluded, or solutions
+    /// must be disjoint from.
+    UnavailableLetter(char),
+    /// It has no vowel, and the solver requires one.
+    NoVowel,
+    /// Its spelling contains this forbidden bigram.
+    ForbiddenBigram(String),
+    /// It isn't one of the dictionary's words.
+    NotInDictionary,
+    /// It isn't in the list the solver intersects with.
+    NotInIntersection,
+    /// No other spelling of its letters is in the dictionary, and the
+    /// solver requires one.
+    NotAnagrammable,
+    /// It's a candidate, but no solution uses it.
+    NoSolutions,
+    /// It's in `count` solutions. When the dictionary spells its
+    /// letters some other way first, the solutions show `anagram`
+    /// instead.
+    Solutions {
+        count: usize,
+        anagram: Option<String>,
+    },
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Explanation::NotALetter(c) => write!(f, "rejected: {:?} isn't a plain letter", c),
+            Explanation::RepeatedLetter(c) => {
+                write!(f, "rejected: '{}' is used more than once", c)
+            }
+            Explanation::WrongLength(len) => {
+                write!(f, "rejected: it has {} letters, not the word length", len)
+            }
+            Explanation::UnavailableLetter(c) => {
+                write!(f, "rejected: '{}' isn't allowed, or is excluded", c)
+            }
+            Explanation::NoVowel => write!(f, "rejected: it has no vowel"),
+            Explanation::ForbiddenBigram(bigram) => {
+                write!(f, "rejected: it contains the forbidden '{}'", bigram)
+            }
+            Explanation::NotInDictionary => write!(f, "rejected: it isn't in the dictionary"),
+            Explanation::NotInIntersection => {
+                write!(f, "rejected: it isn't in the intersected list")
+            }
+            Explanation::NotAnagrammable => write!(
+                f,
+                "rejected: no other spelling of its letters is in the dictionary"
+            ),
+            Explanation::NoSolutions => write!(f, "a candidate, but in no solution"),
+            Explanation::Solutions { count, anagram } => {
+                write!(
+                    f,
+                    "in {} solution{}",
+                    count,
+                    if *count == 1 { "" } else { "s" }
+                )?;
+
+                match anagram {
+                    Some(anagram) => write!(f, " (as its anagram '{}')", anagram),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+// run the checks building the index would, in the same order, on the
+// one word. A word passing them all is a candidate: we return its
+// letters, their plain mask, and the (first) spelling solutions show
+// them as.
+//
+pub(crate) fn check(
+    config: &Config,
+    dict: &PreparedDict,
+    word: &str,
+) -> Result<(ZWord, u64, ZWord), Explanation> {
+    let word = word.trim();
+
+    let (own, bits) = read_letters(word).map_err(|problem| match problem {
+        Problem::NotALetter(c) => Explanation::NotALetter(c),
+        Problem::RepeatedLetter(c) => Explanation::RepeatedLetter(c),
+        Problem::WrongLength(len) => Explanation::WrongLength(len),
+    })?;
+
+    let len = word.chars().count();
+
+    if !config.lengths().contains(&len) {
+        return Err(Explanation::WrongLength(len));
+    }
+
+    let unavailable = bits & !config.usable();
+
+    if unavailable != 0 {
+        return Err(Explanation::UnavailableLetter(
+            ZChar(unavailable.trailing_zeros() as u8).chr(),
+        ));
+    }
+
+    if config.require_vowel && bits & config.vowels() == 0 {
+        return Err(Explanation::NoVowel);
+    }
+
+    if let Some(bigram) = config.forbidden_in(word) {
+        return Err(Explanation::ForbiddenBigram(bigram.into()));
+    }
+
+    // every spelling of these letters that survives intersecting (and
+    // has no forbidden bigram); the index keeps the first, or the
+    // alphabetically first
+    //
+    let listed = |spelling: &str| {
+        config.forbidden_in(spelling).is_none()
+            && config
+                .intersect
+                .as_ref()
+                .is_none_or(|common| common.contains(spelling))
+    };
+
+    let ours = dict
+        .entries
+        .iter()
+        .filter(|entry| entry.spelling.eq_ignore_ascii_case(word))
+        .collect::<Vec<_>>();
+
+    if ours.is_empty() {
+        return Err(Explanation::NotInDictionary);
+    }
+    if !ours.iter().any(|entry| listed(&entry.spelling)) {
+        return Err(Explanation::NotInIntersection);
+    }
+
+    let spellings: Vec<ZWord> = dict
+        .entries
+        .iter()
+        .filter(|entry| entry.bits == bits && listed(&entry.spelling))
+        .map(|entry| entry.word)
+        .collect();
+
+    let first = match config.anagram {
+        Anagram::First => spellings.first(),
+        Anagram::Alpha => spellings
+            .iter()
+            .min_by(|a, b| a.iter().map(|z| z.ord()).cmp(b.iter().map(|z| z.ord()))),
+    };
+    let first = *first.expect("our own spelling is listed");
+
+    if config.require_anagrammable && spellings.iter().all(|word| word.iter().eq(first.iter())) {
+        return Err(Explanation::NotAnagrammable);
+    }
+
+    Ok((own, bits, first))
+}
@@ -0,0 +1,366 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::mask::Mask;
+use crate::prepared::PreparedDict;
+use crate::shim::{Entry, Map, Set};
+use crate::solution::Solution;
+use crate::solver::{Anagram, Config, Dedup};
+use crate::word::{ZChar, ZWord};
+
+// how many letters there are, and the bits of a (plain) mask that
+// correspond to them
+//
+pub(crate) const ALPHABET_LEN: usize = 26;
+pub(crate) const ALPHABET_MASK: u64 = (1 << ALPHABET_LEN) - 1;
+
+// the structures the search runs over: candidate masks bucketed by
+// their lowest (remapped) bit, the word each mask came from, the
+// mappings between letters and their remapped bits, and the
+// (remapped) letters solutions may use at all.
+//
+// There's a bucket (and a bit) for each of the alphabet's `symbols`:
+// the 26 letters, or as many as an alphabet past 'z' has.
+//
+#[derive(Default)]
+pub(crate) struct Index<M: Mask> {
+    pub symbols: u32,
+    pub lbit_lut: Vec<M>,
+    pub lbit_range: Vec<(u32, u32)>,
+    pub word_lut: Map<M, ZWord>,
+    pub mask_lut: Vec<(M, usize)>,
+    pub bit_to_char: Vec<ZChar>,
+    pub alphabet: M,
+
+    // the candidates, one spelling per set of letters, in dictionary
+    // order
+    //
+    pub words: Vec<ZWord>,
+
+    // the (remapped) bits whose buckets hold any words
+    //
+    pub filled: M,
+
+    // every spelling of the letter sets that have more than one, when
+    // we keep them all (dedup by original spelling)
+    //
+    pub spellings: Map<M, Vec<ZWord>>,
+}
+
+impl<M: Mask> Index<M> {
+    pub fn build(dict: &PreparedDict, config: &Config) -> Self {
+        let mut seen: Map<u64, ZWord> = Default::default();
+        let mut anagrammed: Set<u64> = Default::default();
+        let mut spellings: Map<u64, Vec<ZWord>> = Default::default();
+        let mut words: Vec<_> = Vec::with_capacity(6000);
+        let mut freq: Vec<(ZChar, u32)> =
+            (0..config.symbols()).map(|i| (ZChar(i as u8), 0)).collect();
+
+        let usable = config.usable();
+        let vowels = config.vowels();
+
+        for entry in &dict.entries {
+            // we are looking for words of the target length ONLY!
+            //
+            if !config.lengths().contains(&entry.word.len()) {
+                continue;
+            }
+
+            // nor are words using letters we have excluded (or that
+            // solutions must leave unused)
+            //
+            if entry.bits & !usable != 0 {
+                continue;
+            }
+
+            // and, for themed puzzles, words made only of consonants
+            //
+            if config.require_vowel && entry.bits & vowels == 0 {
+                continue;
+            }
+
+            // nor (for style) spellings with a forbidden bigram, which
+            // is again checked before anagrams are collapsed, so a
+            // spelling without one can stand in for its letters
+            //
+            if config.forbidden_in(&entry.spelling).is_some() {
+                continue;
+            }
+
+            // when intersecting with a second list, check the original
+            // spelling here, BEFORE anagrams are collapsed below, so any
+            // spelling in both lists can stand in for its letter set.
+            //
+            if let Some(common) = &config.intersect {
+                if !common.contains(&*entry.spelling) {
+                    continue;
+                }
+            }
+
+            // increase the letter frequency counts
+            //
+            for z in &entry.word {
+                freq[z.ord()].1 += 1;
+            }
+
+            // we don't need anagrams of words, so just take the first
+            // anagram (the unique alphabet bit-pattern).
+            //
+            // (but note which letter sets have more than one spelling)
+            //
+            match seen.entry(entry.bits) {
+                Entry::Vacant(first) => {
+                    first.insert(entry.word);
+                    words.push(entry.word);
+                }
+                Entry::Occupied(mut first) => {
+                    if !first.get().iter().eq(entry.word.iter()) {
+                        anagrammed.insert(entry.bits);
+
+                        if config.dedup == Dedup::Original {
+                            let all = spellings.entry(entry.bits).or_insert(vec![*first.get()]);
+
+                            // (the same letters twice, like "Polish" and
+                            // "polish", are the one spelling)
+                            //
+                            if !all.iter().any(|w| w.iter().eq(entry.word.iter())) {
+                                all.push(entry.word);
+                            }
+                        }
+                    }
+
+                    // (and, when asked, the alphabetically first spelling
+                    // stands for the letters, rather than the dictionary's)
+                    //
+                    if config.anagram == Anagram::Alpha
+                        && entry
+                            .word
+                            .iter()
+                            .map(|z| z.ord())
+                            .lt(first.get().iter().map(|z| z.ord()))
+                    {
+                        first.insert(entry.word);
+                    }
+                }
+            }
+        }
+
+        // the candidates keep their place in the dictionary, whichever
+        // spelling they take
+        //
+        if config.anagram == Anagram::Alpha {
+            for word in &mut words {
+                *word = seen[&word.iter().fold(0, |acc, z| acc | z.mask())];
+            }
+        }
+
+        // when every word needs an alternative spelling, letter sets
+        // with only the one can never be part of a solution
+        //
+        if config.require_anagrammable {
+            words.retain(|word| anagrammed.contains(&word.iter().fold(0, |acc, z| acc | z.mask())));
+        }
+
+        // an outside corpus, when we have one, replaces the counts of
+        // the candidates themselves
+        //
+        if let Some(counts) = &config.letter_freq {
+            for (f, &count) in freq.iter_mut().zip(counts) {
+                f.1 = count;
+            }
+        }
+
+        // letters with equal counts are ordered alphabetically, so the
+        // bit each letter gets (and the order of the search) is fully
+        // determined by the dictionary
+        //
+        freq.sort_unstable_by_key(|&(z, count)| (count, z.ord()));
+
+        let order: Vec<ZChar> = freq.iter().map(|&(z, _)| z).collect();
+        let mut index = build_index(words, &order);
+
+        for (bits, mut all) in spellings {
+            // (keeping only the first few spellings, when capped)
+            //
+            if let Some(max) = config.max_spellings {
+                all.sort_unstable_by(|a, b| {
+                    a.iter().map(|z| z.ord()).cmp(b.iter().map(|z| z.ord()))
+                });
+                all.truncate(max);
+            }
+
+            index.spellings.insert(index.remap(bits), all);
+        }
+
+        index.alphabet = index.remap(config.alphabet());
+        index
+    }
+
+    // the candidates whose lowest (remapped) bit is `lowbit`
+    //
+    #[inline]
+    pub fn bucket(&self, lowbit: u32) -> &[M] {
+        let (start, len) = self.lbit_range[lowbit as usize];
+
+        &self.lbit_lut[start as usize..(start + len) as usize]
+    }
+
+    // how many candidates there are, counting each spelling we keep
+    //
+    pub fn candidates(&self) -> usize {
+        self.word_lut.len()
+            + self
+                .spellings
+                .values()
+                .map(|all| all.len() - 1)
+                .sum::<usize>()
+    }
+
+    // turn a mask over the plain alphabet (bit 0 = 'a') into the
+    // remapped bits the search works with
+    //
+    pub fn remap(&self, mut raw: u64) -> M {
+        let mut bits = M::ZERO;
+
+        while raw != 0 {
+            bits |= self.mask_lut[raw.trailing_zeros() as usize].0;
+            raw &= raw - 1;
+        }
+
+        bits
+    }
+
+    pub fn solution(&self, selected: &[M]) -> Solution {
+        // the unused letters are whichever alphabet bits none of the
+        // selected words cover (NOT the final search mask, which has
+        // the skipped bits set).
+        //
+        let used = selected.iter().fold(M::ZERO, |acc, &bits| acc | bits);
+
+        Solution {
+            words: selected.iter().map(|bits| self.word_lut[bits]).collect(),
+            unused: self.decode(!used & self.alphabet),
+            masks: selected.iter().map(|&bits| bits.widen()).collect(),
+        }
+    }
+
+    // a solution once for each way of spelling its words
+    //
+    pub fn spelled(&self, selected: &[M]) -> Vec<Solution> {
+        let mut solutions = vec![self.solution(selected)];
+
+        for (i, bits) in selected.iter().enumerate() {
+            if let Some(all) = self.spellings.get(bits) {
+                solutions = solutions
+                    .iter()
+                    .flat_map(|solution| {
+                        all.iter().map(move |&word| {
+                            let mut solution = solution.clone();
+                            solution.words[i] = word;
+                            solution
+                        })
+                    })
+                    .collect();
+            }
+        }
+
+        solutions
+    }
+
+    // turn a remapped mask back into its letters, in alphabetical order
+    //
+    pub fn decode(&self, mut mask: M) -> Vec<ZChar> {
+        let mut letters = Vec::with_capacity(mask.count_ones() as usize);
+
+        while mask != M::ZERO {
+            letters.push(self.bit_to_char[mask.trailing_zeros() as usize]);
+            mask = mask.clear_lowest();
+        }
+
+        letters.sort_unstable_by_key(ZChar::ord);
+        letters
+    }
+}
+
+// lay out the buckets for `words` (the candidates, in dictionary
+// order), giving the letters of `order` (every symbol of the alphabet)
+// the bits from 0 up, so the first is the rarest
+//
+pub(crate) fn build_index<M: Mask>(words: Vec<ZWord>, order: &[ZChar]) -> Index<M> {
+    // build bitmask LUT from frequencies. The idea is that each
+    // character gets assigned a new bit position, based upon its
+    // frequency in the valid words.
+    //
+    // eg:
+    //   ('a' x 100) : mask_lut[0] = (0b...0000_0000_0000_0001, 0)
+    //   ('e' x 89)  : mask_lut[4] = (0b...0000_0000_0000_0010, 1)
+    //   ('i' x 77)  : mask_lut[8] = (0b...0000_0000_0000_0100, 2)
+    //
+    let symbols = order.len();
+    let mut index = Index {
+        symbols: symbols as u32,
+        lbit_range: vec![(0, 0); symbols],
+        mask_lut: vec![(M::ZERO, 0); symbols],
+        bit_to_char: vec![ZChar::default(); symbols],
+        ..Default::default()
+    };
+
+    for (i, &z) in order.iter().enumerate() {
+        index.mask_lut[z.ord()] = (M::bit(i as u32), i);
+        index.bit_to_char[i] = z;
+    }
+
+    // give each word a new mask, where the most-frequent letters
+    // appear closer to the LSB (least significant bit) in the
+    // bitfield.
+    //
+    // eg: "cats"
+    // ('c' x 20)  = 0b...0000_0100_0000_0000
+    // ('a' x 100) = 0b...0000_0000_0000_0001  < most freq' so more LSB
+    // ('t' x 33)  = 0b...0000_0000_0100_0000
+    // ('s' x 40)  = 0b...0000_0000_0000_1000
+    //
+    // We also stick all words with the same LSB into a bucket, so
+    // we can easily look them up. This means we can EFFICIENTLY
+    // fil a target bit-pattern quickly.
+    //
+    let mut bucketed = Vec::with_capacity(words.len());
+
+    for &word in &words {
+        let mut new_bits = M::ZERO;
+        let mut lowbit = symbols;
+
+        for z in word {
+            let idx = z.ord();
+            let msk = index.mask_lut[idx].0;
+            let lsb = index.mask_lut[idx].1;
+
+            new_bits |= msk;
+
+            lowbit = lowbit.min(lsb);
+        }
+
+        bucketed.push((lowbit, new_bits));
+        index.word_lut.insert(new_bits, word);
+        index.filled |= M::bit(lowbit as u32);
+    }
+
+    // lay the buckets out end to end (each in dictionary order, as
+    // the sort is stable), so the search runs along one contiguous
+    // array
+    //
+    bucketed.sort_by_key(|&(lowbit, _)| lowbit);
+
+    for (i, &(lowbit, bits)) in bucketed.iter().enumerate() {
+        let range = &mut index.lbit_range[lowbit];
+
+        if range.1 == 0 {
+            range.0 = i as u32;
+        }
+        range.1 += 1;
+        index.lbit_lut.push(bits);
+    }
+
+    index.words = words;
+    index
+}
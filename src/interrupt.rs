@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+
+// the flag every solver is cancelled by, and whether Ctrl-C has been
+// pressed (which is what sets it)
+//
+static CANCEL: LazyLock<Arc<AtomicBool>> = LazyLock::new(Default::default);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+// with the 'ctrlc' feature, the first Ctrl-C winds the search down so
+// the run can still print what it found; a second quits there and then
+//
+pub fn install() {
+    #[cfg(feature = "ctrlc")]
+    if let Err(err) = ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::Relaxed) {
+            std::process::exit(130);
+        }
+
+        eprintln!("interrupted: finishing up (Ctrl-C again to quit now)");
+        CANCEL.store(true, Ordering::Relaxed);
+    }) {
+        eprintln!("warning: can't handle Ctrl-C: {}", err);
+    }
+}
+
+pub fn cancel_flag() -> Arc<AtomicBool> {
+    CANCEL.clone()
+}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
@@ -0,0 +1,154 @@
+//! Finds sets of words that share no letters: the classic puzzle of
+//! five five-letter words covering 25 distinct letters, generalized
+//! over word length, set size and letter constraints.
+//!
+//! ```no_run
+//! use fivewords::SolverBuilder;
+//!
+//! let dict = std::fs::read_to_string("words.txt").unwrap();
+//! let solver = SolverBuilder::new().word_len(5).set_size(5).build().unwrap();
+//!
+//! for solution in solver.solve(&dict) {
+//!     println!("{}", solution);
+//! }
+//! ```
+//!
+//! Without the default `std` feature the library is `no_std` (it still
+//! needs `alloc`): the search then runs on a single thread, and the
+//! [`Stats`] timings are zero.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::shim::Set;
+
+mod coverage;
+mod event;
+mod explain;
+mod index;
+mod mask;
+#[cfg(feature = "unicode")]
+mod normalize;
+mod pairs;
+mod prepared;
+#[cfg(feature = "pyo3")]
+mod python;
+mod search;
+mod selftest;
+mod shim;
+mod solution;
+mod solver;
+mod word;
+
+pub use event::Event;
+pub use explain::Explanation;
+#[cfg(feature = "unicode")]
+pub use normalize::Normalize;
+pub use pairs::LetterPairs;
+pub use prepared::{MalformedWord, NotUtf8, PreparedDict, Problem};
+pub use selftest::Check;
+pub use solution::Solution;
+pub use solver::{
+    Anagram, Backend, Dedup, Error, Order, Progress, Seed, SeedOrder, Solver, SolverBuilder, Stats,
+};
+pub use word::{ZChar, ZWord, MAX_SYMBOLS};
+
+/// How many distinct candidate words of length `len` are in `words`:
+/// words of distinct plain letters, with anagrams counted once. This
+/// is the `candidates` count a solve's [`Stats`] would report, without
+/// doing the search.
+pub fn count_candidates(words: &[&str], len: usize) -> usize {
+    let dict = PreparedDict::from_words(words.iter().copied());
+
+    dict.entries
+        .iter()
+        .filter(|entry| entry.word.len() == len && entry.bits & !index::ALPHABET_MASK == 0)
+        .map(|entry| entry.bits)
+        .collect::<Set<_>>()
+        .len()
+}
+
+/// The distinct candidate words of length `len` in `dict` (one word
+/// per line), in dictionary order, each with the plain mask of its
+/// letters (bit 0 = 'a', before the search remaps them by frequency).
+/// These are the words a solve indexes: each of distinct plain letters,
+/// with a set of anagrams given once, as the dictionary first spells
+/// it.
+pub fn candidates(dict: &str, len: usize) -> impl Iterator<Item = (String, u32)> {
+    // (one word at a time, so no length is too long for the alphabet)
+    //
+    let words = SolverBuilder::new()
+        .word_len(len)
+        .set_size(1)
+        .build()
+        .map(|solver| solver.candidates(&PreparedDict::new(dict)))
+        .unwrap_or_default();
+
+    // (the default alphabet is the 26 letters, so the masks fit)
+    //
+    words.into_iter().map(|word| {
+        let bits = word.iter().fold(0, |acc, z| acc | z.mask());
+
+        (word.to_string(), bits as u32)
+    })
+}
+
+/// The letters of a plain mask (bit 0 = 'a'), as [`candidates`] gives
+/// them, in alphabetical order. Bits past 'z' are ignored.
+pub fn decode_mask(mask: u32) -> Vec<char> {
+    (0..index::ALPHABET_LEN as u8)
+        .filter(|&i| mask & 1 << i != 0)
+        .map(|i| ZChar(i).chr())
+        .collect()
+}
+
+/// The buckets the search would lay `words` out in, with `order`
+/// giving the letters from the rarest (the search's first) to the
+/// most common: each word goes in the bucket of its rarest letter,
+/// and each bucket keeps its words in the order given. The result has
+/// one bucket per letter, in `order`'s order.
+///
+/// # Panics
+///
+/// If `order` names any letter twice, or `words` repeats a set of
+/// letters.
+///
+/// This exposes a detail of the index so its construction can be
+/// tested on its own: it isn't part of the stable API, and may change
+/// or go away with the index.
+#[doc(hidden)]
+pub fn buckets(words: &[ZWord], order: &[ZChar; 26]) -> Vec<Vec<ZWord>> {
+    assert!(
+        order.iter().map(ZChar::ord).collect::<Set<_>>().len() == order.len(),
+        "the order must name every letter once"
+    );
+
+    let index = index::build_index::<u32>(words.to_vec(), order);
+
+    assert!(
+        index.word_lut.len() == words.len(),
+        "the words must have distinct sets of letters"
+    );
+
+    (0..order.len() as u32)
+        .map(|bit| {
+            index
+                .bucket(bit)
+                .iter()
+                .map(|bits| index.word_lut[bits])
+                .collect()
+        })
+        .collect()
+}
+
+/// Every solution to the classic puzzle in `dict` (one word per line).
+pub fn find_word_sets(dict: &str) -> Vec<Solution> {
+    SolverBuilder::new()
+        .build()
+        .expect("the default solver is valid")
+        .solve(dict)
+}
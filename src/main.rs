@@ -1,229 +1,3542 @@
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
 use std::fmt::{Display, Formatter};
 use std::fs;
-use std::ops::{Deref, DerefMut};
-use std::time::Instant;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::process::exit;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "encoding")]
+use encoding_rs::Encoding;
+#[cfg(feature = "unicode")]
+use fivewords::Normalize;
+use fivewords::{
+    Anagram, Backend, Dedup, Event, Order, PreparedDict, Problem, SeedOrder, Solution, Solver,
+    SolverBuilder, Stats, ZChar, ZWord, MAX_SYMBOLS,
+};
+
+use checkpoint::{Checkpoint, Interval};
+use manifest::DEFAULT_MANIFEST;
+use summary::{print_letters_report, LengthCounts, Summary, WordHistogram};
+use template::Template;
+use tokens::Tokens;
+
+mod binary;
+mod checkpoint;
+mod dict_info;
+mod interrupt;
+mod manifest;
+mod output_limit;
+#[cfg(feature = "progress")]
+mod progress_bar;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod summary;
+mod template;
+mod tokens;
+mod top_words;
+mod word_square;
+
+// what a --shortlist is called where a dictionary's path would be
+//
+const SHORTLIST: &str = "shortlist";
+
+// how many of a dictionary's malformed lines --strict reports
+//
+const MAX_MALFORMED: usize = 20;
 
 fn main() {
-    let words = fs::read_to_string("words.txt").unwrap();
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    // `fivewords selftest ...` is --self-test by another name
+    //
+    if args.first().is_some_and(|arg| arg == "selftest") {
+        args[0] = "--self-test".to_string();
+    }
+
+    interrupt::install();
+
+    let (mut opts, args) = match Options::with_preset(args) {
+        Ok(parsed) => parsed,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            exit(2);
+        }
+    };
+
+    if let Some(bytes) = opts.output_limit {
+        output_limit::set(bytes);
+    }
+
+    if let Some(path) = &opts.event_log {
+        match fs::File::create(path) {
+            Ok(file) => opts.events = Some(Arc::new(Mutex::new(io::BufWriter::new(file)))),
+            Err(err) => {
+                eprintln!("error: can't write event log '{}': {}", path, err);
+                exit(2);
+            }
+        }
+    }
+
+    let solver = match opts.builder().build() {
+        Ok(solver) => solver,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            exit(2);
+        }
+    };
+
+    // a --shortlist stands in for the dictionary, under its own name
+    //
+    if opts.paths.is_empty() {
+        opts.paths.push(match opts.shortlist {
+            Some(_) => SHORTLIST.to_string(),
+            None => "words.txt".to_string(),
+        });
+    }
+
+    if let Some(path) = &opts.decode_binary {
+        decode_binary(path, &opts);
+        return;
+    }
+
+    if opts.benchmark_dictionaries {
+        benchmark_dictionaries(&solver, &opts);
+        return;
+    }
+
+    if opts.compare {
+        compare_dictionaries(&solver, &opts);
+        return;
+    }
+
+    // diagnostics only, no search
+    //
+    if opts.dict_info {
+        for (i, path) in opts.paths.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+
+            match opts.read_words(path) {
+                Ok(text) => dict_info::report(path, &text, opts.word_len),
+                Err(err) => {
+                    println!("dictionary: {}", path);
+                    println!("  error: {}", err);
+                }
+            }
+        }
+        return;
+    }
+
+    if opts.paths.len() > 1 {
+        eprintln!("error: expected a single dictionary (use --benchmark-dictionaries for several)");
+        exit(2);
+    }
+
+    if opts.dry_parse {
+        dry_parse(&solver, &opts);
+        return;
+    }
+
+    // a curated list shouldn't have anything for the solver to skip
+    //
+    if opts.strict {
+        let words = match opts.read_words(&opts.paths[0]) {
+            Ok(words) => words,
+            Err(err) => {
+                eprintln!("error: can't read dictionary '{}': {}", opts.paths[0], err);
+                exit(2);
+            }
+        };
+
+        // (every malformed line, so they can all be fixed in one go,
+        // but not so many that they bury the rest of the output)
+        //
+        let malformed: Vec<_> = solver.malformed(&words).collect();
+
+        for word in malformed.iter().take(MAX_MALFORMED) {
+            eprintln!("error: {}: {}", opts.paths[0], word);
+        }
+        if malformed.len() > MAX_MALFORMED {
+            eprintln!(
+                "error: {}: and {} more malformed lines",
+                opts.paths[0],
+                malformed.len() - MAX_MALFORMED
+            );
+        }
+        if !malformed.is_empty() {
+            exit(2);
+        }
+    }
+
+    // everything from here on only needs the words themselves, so the
+    // dictionary is indexed as it's read rather than held as text
+    //
+    let (dict, dict_hash) = match opts.stream_words(&solver, &opts.paths[0]) {
+        Ok(read) => read,
+        Err(err) => {
+            eprintln!("error: can't read dictionary '{}': {}", opts.paths[0], err);
+            exit(2);
+        }
+    };
+
+    if opts.casing == Casing::AsInput {
+        opts.spellings = Arc::new(spellings(&dict));
+    }
+
+    if let Some(path) = &opts.id_table {
+        opts.ids = Arc::new(write_id_table(&solver, &dict, path, &opts));
+    }
+
+    // diagnostics for a few words, rather than the solutions
+    //
+    if !opts.explain.is_empty() {
+        for word in &opts.explain {
+            println!("{}: {}", word, solver.explain(&dict, word));
+        }
+        return;
+    }
+
+    if opts.validate_dictionary {
+        validate_dictionary(&solver, &dict);
+        return;
+    }
+
+    if opts.self_test {
+        self_test(&solver, &dict, &opts.paths[0]);
+        return;
+    }
+
+    if opts.profile_buckets {
+        profile_buckets(&solver, &dict);
+        return;
+    }
+
+    if opts.suggest {
+        suggest(&solver, &dict, &opts);
+        return;
+    }
+
+    if opts.graph_stats {
+        graph_stats(&solver, &dict);
+        return;
+    }
+
+    if let Some(path) = &opts.dump_index {
+        dump_index(&solver, &dict, path);
+        return;
+    }
+
+    if let Some(rarest) = opts.letter_pairs {
+        println!("{}", solver.letter_pairs(&dict, rarest));
+        return;
+    }
+
+    // (each query of a batch picks its own word length)
+    //
+    // (and a --len the dictionary has no words of at all is left to
+    // the hint once it's solved)
+    //
+    if !opts.no_warn && opts.batch.is_none() && opts.suggested_len(&dict).is_none() {
+        warn_of_lengths(&dict, opts.lengths());
+    }
+
+    // (a word the dictionary doesn't have would just find nothing)
+    //
+    if let Some(word) = &opts.must_include {
+        if !is_candidate(&solver, &dict, word) {
+            eprintln!(
+                "error: --must-include: '{}' isn't a usable word of '{}'",
+                word, opts.paths[0]
+            );
+            exit(2);
+        }
+    }
+
+    let timer = Instant::now();
+
+    let found = match (&opts.batch, &opts.resume) {
+        (Some(path), _) => run_batch(&dict, dict_hash, path, &args),
+        (None, Some(path)) => run_resumable(&solver, &dict, dict_hash, path, &opts),
+        (None, None) if opts.word_square => run_word_squares(&solver, &dict, &opts),
+        (None, None) => match opts.min_coverage {
+            Some(coverage) => run_best_coverage(&dict, dict_hash, coverage, &opts),
+            None if opts.retry_without_skip => run_retrying(&dict, dict_hash, &opts),
+            None => run(&solver, &dict, dict_hash, &opts),
+        },
+    };
+
+    if let Some(log) = &opts.events {
+        if let Err(err) = log.lock().unwrap().flush() {
+            eprintln!("error: can't write event log: {}", err);
+            exit(2);
+        }
+    }
+
+    // keep stdout clean for machine-readable formats (and the same from
+    // run to run, for --canonical-output, and within the limit, for
+    // --output-limit-bytes)
+    //
+    match opts.format {
+        Format::Text if !opts.canonical && opts.output_limit.is_none() => {
+            println!("Elapsed ms [{}]", timer.elapsed().as_millis())
+        }
+        Format::Text | Format::Json | Format::Binary | Format::Ids => {
+            eprintln!("Elapsed ms [{}]", timer.elapsed().as_millis())
+        }
+    }
+
+    // --fail-on-no-solution: like grep, finding nothing exits 1 (bad
+    // input is still 2)
+    //
+    if opts.fail_on_no_solution && found == 0 {
+        exit(1);
+    }
+}
+
+#[derive(Default, Clone)]
+struct Options {
+    paths: Vec<String>,
+    word_len: usize,
+    max_len: Option<usize>,
+    // whether --len (or --len-range) was given, rather than the default
+    //
+    len_given: bool,
+    set_size: usize,
+    no_skip: bool,
+    retry_without_skip: bool,
+    allowed: Option<String>,
+    alphabet_range: Option<String>,
+    locale: Option<String>,
+    required: Option<String>,
+    excluded: Option<String>,
+    disjoint: Option<String>,
+    must_include: Option<String>,
+    limit: Option<usize>,
+    first_only: bool,
+    per_letter_limit: Option<usize>,
+    order: Order,
+    seed_order: SeedOrder,
+    backend: Backend,
+    threads: Option<usize>,
+    warm_pool: bool,
+    time_budget: Option<Duration>,
+    dedup: Dedup,
+    anagram: Anagram,
+    max_spellings: Option<usize>,
+    trace_depth: Option<usize>,
+    format: Format,
+    show_unused: bool,
+    unused_only: bool,
+    show_masks: bool,
+    group_by_first: bool,
+    grid: bool,
+    template: Option<Template>,
+    intersect: Option<String>,
+    forbidden: Vec<String>,
+    shortlist: Option<String>,
+    freq_source: Option<String>,
+    top_words: Option<usize>,
+    require_anagrammable: bool,
+    require_vowel: bool,
+    y_vowel: bool,
+    resume: Option<String>,
+    checkpoint_interval: Option<Interval>,
+    batch: Option<String>,
+    batch_dedup: bool,
+    fail_on_no_solution: bool,
+    canonical: bool,
+    stream_immediate: bool,
+
+    // the solutions the batch has printed so far, for --batch-dedup, and
+    // the line of the query being answered
+    //
+    shown: Option<Arc<Shown>>,
+    query: usize,
+    #[cfg(feature = "unicode")]
+    normalize: Normalize,
+    #[cfg(feature = "encoding")]
+    encoding: Option<&'static Encoding>,
+    benchmark_dictionaries: bool,
+    compare: bool,
+    decode_binary: Option<String>,
+    dict_info: bool,
+    dry_parse: bool,
+    output_limit: Option<u64>,
+    id_table: Option<String>,
+    event_log: Option<String>,
+
+    // the --event-log, once it's open
+    //
+    events: Option<Arc<Mutex<io::BufWriter<fs::File>>>>,
+
+    // each word's number in the --id-table, for --format ids
+    //
+    ids: Arc<HashMap<String, usize>>,
+    explain: Vec<String>,
+    strict: bool,
+    no_warn: bool,
+    validate_dictionary: bool,
+    self_test: bool,
+    letter_pairs: Option<usize>,
+    profile_buckets: bool,
+    graph_stats: bool,
+    dump_index: Option<String>,
+    suggest: bool,
+    used: String,
+    sqlite: Option<String>,
+    mmap: bool,
+    #[cfg(feature = "progress")]
+    progress_bar: bool,
+    casing: Casing,
+    tokens: Option<Tokens>,
+    contains: Option<String>,
+    word_square: bool,
+
+    // each word's first spelling in the dictionary, for --normalize-output
+    // as-input (filled in once the dictionary is read)
+    //
+    spellings: Arc<HashMap<String, String>>,
+    min_coverage: Option<usize>,
+    min_unique: Option<usize>,
+    coverage: Option<usize>,
+    coverage_mode: Option<CoverageMode>,
+
+    // the most letters a set may cover, once --coverage is worked out
+    //
+    max_unique: Option<usize>,
+    summary: bool,
+    summary_only: bool,
+    letters_report: bool,
+    histogram: bool,
+    by_length: bool,
+    preset: Option<String>,
+    manifest: Option<String>,
+}
+
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
+enum Format {
+    #[default]
+    Text,
+    Json,
+    Binary,
+    Ids,
+}
+
+impl std::str::FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "binary" => Ok(Format::Binary),
+            "ids" => Ok(Format::Ids),
+            _ => Err(()),
+        }
+    }
+}
+
+// how words are cased wherever they're printed (--normalize-output).
+// The search only sees lowercase letters, so `AsInput` looks each word
+// up in the dictionary again.
+//
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+enum Casing {
+    #[default]
+    Lower,
+    AsInput,
+    Upper,
+}
+
+impl std::str::FromStr for Casing {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lower" => Ok(Casing::Lower),
+            "as-input" => Ok(Casing::AsInput),
+            "upper" => Ok(Casing::Upper),
+            _ => Err(()),
+        }
+    }
+}
+
+// whether a set's words cover exactly the --coverage letters, or at
+// least that many
+//
+#[derive(Default, Copy, Clone, PartialEq, Eq)]
+enum CoverageMode {
+    #[default]
+    Exact,
+    AtLeast,
+}
+
+impl std::str::FromStr for CoverageMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(CoverageMode::Exact),
+            "at-least" => Ok(CoverageMode::AtLeast),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Options {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut opts = Options {
+            word_len: 5,
+            set_size: 5,
+            ..Default::default()
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--len" => {
+                    opts.word_len = parse_value(&arg, args.next())?;
+                    opts.max_len = None;
+                    opts.len_given = true;
+                }
+                "--len-range" => {
+                    let range: String = parse_value(&arg, args.next())?;
+                    let (min, max) = length_range(&range)?;
+
+                    opts.word_len = min;
+                    opts.max_len = Some(max);
+                    opts.len_given = true;
+                }
+                "--count" | "--group-size" => opts.set_size = parse_value(&arg, args.next())?,
+                "--no-skip" => opts.no_skip = true,
+                "--retry-without-skip" => opts.retry_without_skip = true,
+                "--allowed" => opts.allowed = Some(parse_value(&arg, args.next())?),
+                "--alphabet-range" => opts.alphabet_range = Some(parse_value(&arg, args.next())?),
+                "--locale" => opts.locale = Some(parse_value(&arg, args.next())?),
+                "--required" => opts.required = Some(parse_value(&arg, args.next())?),
+                "--exclude" => opts.excluded = Some(parse_value(&arg, args.next())?),
+                "--require-disjoint-from" => opts.disjoint = Some(parse_value(&arg, args.next())?),
+                "--must-include" => opts.must_include = Some(parse_value(&arg, args.next())?),
+                "--limit" => opts.limit = Some(parse_value(&arg, args.next())?),
+                "--first-only" => opts.first_only = true,
+                "--per-letter-limit" => {
+                    opts.per_letter_limit = Some(parse_value(&arg, args.next())?);
+                }
+                "--order" | "--sort-by" => opts.order = parse_value(&arg, args.next())?,
+                "--seed-order" => opts.seed_order = parse_value(&arg, args.next())?,
+                "--backend" => opts.backend = parse_value(&arg, args.next())?,
+                "--threads" => opts.threads = Some(parse_value(&arg, args.next())?),
+                "--warm-pool" => opts.warm_pool = true,
+                "--time-budget" => {
+                    let budget: String = parse_value(&arg, args.next())?;
+
+                    opts.time_budget = Some(duration(&budget).ok_or_else(|| {
+                        format!(
+                            "invalid time budget '{}' (expected e.g. '2s', '500ms' or '1m')",
+                            budget
+                        )
+                    })?);
+                }
+                "--dedup-by" => opts.dedup = parse_value(&arg, args.next())?,
+                "--select-anagram" => opts.anagram = parse_value(&arg, args.next())?,
+                "--max-anagrams-per-slot" => {
+                    opts.max_spellings = Some(parse_value(&arg, args.next())?)
+                }
+                "--max-depth-trace" => opts.trace_depth = Some(parse_value(&arg, args.next())?),
+                "--format" => opts.format = parse_value(&arg, args.next())?,
+                "--show-unused" => opts.show_unused = true,
+                "--unused-only" => opts.unused_only = true,
+                "--show-masks" => opts.show_masks = true,
+                "--group-by-first" => opts.group_by_first = true,
+                "--grid" => opts.grid = true,
+                "--template" => {
+                    let template: String = parse_value(&arg, args.next())?;
+
+                    opts.template = Some(Template::parse(&template)?);
+                }
+                "--intersect" => opts.intersect = Some(parse_value(&arg, args.next())?),
+                "--forbid-bigram" => {
+                    let bigram: String = parse_value(&arg, args.next())?;
+
+                    if bigram.chars().count() != 2 || !bigram.chars().all(|c| c.is_alphabetic()) {
+                        return Err(format!(
+                            "invalid bigram '{}' (expected two letters)",
+                            bigram
+                        ));
+                    }
+                    opts.forbidden.push(bigram.to_lowercase());
+                }
+                "--shortlist" => {
+                    let words: String = parse_value(&arg, args.next())?;
+
+                    opts.shortlist = Some(
+                        words
+                            .split(|c: char| c == ',' || c.is_whitespace())
+                            .filter(|word| !word.is_empty())
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    );
+                }
+                "--freq-source" => opts.freq_source = Some(parse_value(&arg, args.next())?),
+                "--top-words" => opts.top_words = Some(parse_value(&arg, args.next())?),
+                "--require-anagrammable" => opts.require_anagrammable = true,
+                "--require-vowel" => opts.require_vowel = true,
+                "--y-vowel" => opts.y_vowel = true,
+                "--resume" => opts.resume = Some(parse_value(&arg, args.next())?),
+                "--checkpoint-interval" => {
+                    let interval: String = parse_value(&arg, args.next())?;
+
+                    opts.checkpoint_interval =
+                        Some(checkpoint_interval(&interval).ok_or_else(|| {
+                            format!(
+                            "invalid checkpoint interval '{}' (expected e.g. '10s' or '4seeds')",
+                            interval
+                        )
+                        })?);
+                }
+                "--batch" => opts.batch = Some(parse_value(&arg, args.next())?),
+                "--batch-dedup" => opts.batch_dedup = true,
+                "--fail-on-no-solution" => opts.fail_on_no_solution = true,
+                "--canonical-output" => opts.canonical = true,
+                "--stream-immediate" => opts.stream_immediate = true,
+                #[cfg(feature = "unicode")]
+                "--normalize" => opts.normalize = parse_value(&arg, args.next())?,
+                #[cfg(not(feature = "unicode"))]
+                "--normalize" => {
+                    return Err("--normalize needs a build with the 'unicode' feature".to_string())
+                }
+                #[cfg(feature = "encoding")]
+                "--encoding" => {
+                    let label: String = parse_value(&arg, args.next())?;
+
+                    opts.encoding = Some(
+                        Encoding::for_label(label.as_bytes())
+                            .ok_or_else(|| format!("unknown encoding '{}'", label))?,
+                    );
+                }
+                #[cfg(not(feature = "encoding"))]
+                "--encoding" => {
+                    return Err("--encoding needs a build with the 'encoding' feature".to_string())
+                }
+                "--benchmark-dictionaries" => opts.benchmark_dictionaries = true,
+                "--compare" => opts.compare = true,
+                "--decode-binary" => opts.decode_binary = Some(parse_value(&arg, args.next())?),
+                "--dict-info" => opts.dict_info = true,
+                "--dry-parse" => opts.dry_parse = true,
+                "--event-log" => opts.event_log = Some(parse_value(&arg, args.next())?),
+                "--id-table" => opts.id_table = Some(parse_value(&arg, args.next())?),
+                "--output-limit-bytes" => opts.output_limit = Some(parse_value(&arg, args.next())?),
+                "--strict" => opts.strict = true,
+                "--no-warn" => opts.no_warn = true,
+                "--validate-dictionary" => opts.validate_dictionary = true,
+                "--self-test" => opts.self_test = true,
+                "--profile-buckets" => opts.profile_buckets = true,
+                "--emit-graph-stats" => opts.graph_stats = true,
+                "--suggest" => opts.suggest = true,
+                "--used" => opts.used = parse_value(&arg, args.next())?,
+                "--dump-index" => opts.dump_index = Some(parse_value(&arg, args.next())?),
+                "--word-square" => opts.word_square = true,
+                "--normalize-output" => opts.casing = parse_value(&arg, args.next())?,
+                "--require-contains-substring" | "--contains" => {
+                    let part: String = parse_value(&arg, args.next())?;
+
+                    opts.contains = Some(part.to_lowercase());
+                }
+                "--tokens" => {
+                    let path: String = parse_value(&arg, args.next())?;
+
+                    opts.tokens = Some(Tokens::read(&path)?);
+                }
+                #[cfg(feature = "sqlite")]
+                "--sqlite" => opts.sqlite = Some(parse_value(&arg, args.next())?),
+                #[cfg(not(feature = "sqlite"))]
+                "--sqlite" => {
+                    return Err("--sqlite needs a build with the 'sqlite' feature".to_string())
+                }
+                #[cfg(feature = "progress")]
+                "--progress-bar" => opts.progress_bar = true,
+                #[cfg(not(feature = "progress"))]
+                "--progress-bar" => {
+                    return Err(
+                        "--progress-bar needs a build with the 'progress' feature".to_string()
+                    )
+                }
+                #[cfg(feature = "mmap")]
+                "--mmap" => opts.mmap = true,
+                #[cfg(not(feature = "mmap"))]
+                "--mmap" => return Err("--mmap needs a build with the 'mmap' feature".to_string()),
+                "--letter-pairs" => opts.letter_pairs = Some(parse_value(&arg, args.next())?),
+                "--min-coverage" => opts.min_coverage = Some(parse_value(&arg, args.next())?),
+                "--min-unique" | "--min-unique-across-set" => {
+                    opts.min_unique = Some(parse_value(&arg, args.next())?)
+                }
+                "--coverage" => opts.coverage = Some(parse_value(&arg, args.next())?),
+                "--coverage-mode" => opts.coverage_mode = Some(parse_value(&arg, args.next())?),
+                "--explain" => opts.explain.push(parse_value(&arg, args.next())?),
+                "--summary" => opts.summary = true,
+                "--letters-used-report" => opts.letters_report = true,
+                "--histogram-solutions" => opts.histogram = true,
+                "--count-by-length" => opts.by_length = true,
+                "--use" => opts.preset = Some(parse_value(&arg, args.next())?),
+                "--manifest" => opts.manifest = Some(parse_value(&arg, args.next())?),
+                "--summary-only" => {
+                    opts.summary = true;
+                    opts.summary_only = true;
+                }
+                _ if arg.starts_with("--") => return Err(format!("unknown option '{}'", arg)),
+                _ => opts.paths.push(arg),
+            }
+        }
+
+        // just proof that there is a solution: the limit's stop flag
+        // winds every search thread down as soon as one is found
+        //
+        if opts.first_only {
+            if opts.limit.is_some() {
+                return Err("--first-only can't be combined with --limit".to_string());
+            }
+            opts.limit = Some(1);
+        }
+
+        // a range is just a shorter way of listing the allowed letters
+        //
+        if let Some(range) = &opts.alphabet_range {
+            if opts.allowed.is_some() {
+                return Err("--alphabet-range can't be combined with --allowed".to_string());
+            }
+            opts.allowed = Some(letter_range(range)?);
+        }
+
+        // and a locale, a way of naming a common one
+        //
+        if let Some(name) = &opts.locale {
+            if opts.allowed.is_some() {
+                return Err(
+                    "--locale can't be combined with --allowed or --alphabet-range".to_string(),
+                );
+            }
+            opts.allowed = Some(locale(name)?.to_string());
+        }
+
+        if let Some(template) = &opts.template {
+            if opts.format != Format::Text {
+                return Err("--template only applies to text output".to_string());
+            }
+            if template.max_word() > opts.set_size {
+                return Err(format!(
+                    "template uses {{w{}}} but sets only have {} words",
+                    template.max_word(),
+                    opts.set_size
+                ));
+            }
+        }
+
+        if opts.group_by_first {
+            if opts.format != Format::Text {
+                return Err("--group-by-first only applies to text output".to_string());
+            }
+            if opts.per_letter_limit.is_some() {
+                return Err(
+                    "--group-by-first can't be combined with --per-letter-limit, which groups by unused letter"
+                        .to_string(),
+                );
+            }
+        }
+
+        if opts.unused_only {
+            if opts.format != Format::Text {
+                return Err("--unused-only only applies to text output".to_string());
+            }
+            if opts.template.is_some() || opts.show_masks || opts.group_by_first || opts.grid {
+                return Err(
+                    "--unused-only can't be combined with --template, --show-masks, --group-by-first or --grid"
+                        .to_string(),
+                );
+            }
+        }
+
+        // (these all lean on every word having the one length)
+        //
+        if opts.max_len.is_some_and(|max| max > opts.word_len)
+            && (opts.grid
+                || opts.word_square
+                || opts.min_coverage.is_some()
+                || opts.format == Format::Binary)
+        {
+            return Err(
+                "--len-range can't be combined with --grid, --word-square, --min-coverage or --format binary"
+                    .to_string(),
+            );
+        }
+
+        if opts.grid {
+            if opts.word_len != opts.set_size {
+                return Err(format!(
+                    "--grid needs square solutions, but --len {} differs from --count {}",
+                    opts.word_len, opts.set_size
+                ));
+            }
+            if opts.format != Format::Text {
+                return Err("--grid only applies to text output".to_string());
+            }
+            if opts.template.is_some() || opts.show_masks || opts.group_by_first {
+                return Err(
+                    "--grid can't be combined with --template, --show-masks or --group-by-first"
+                        .to_string(),
+                );
+            }
+        }
+
+        if opts.manifest.is_some() && opts.preset.is_none() {
+            return Err("--manifest only applies with --use".to_string());
+        }
+
+        if opts.checkpoint_interval.is_some() && opts.resume.is_none() {
+            return Err("--checkpoint-interval only applies with --resume".to_string());
+        }
+
+        if opts.resume.is_some() && (!opts.is_streaming() || opts.format != Format::Text) {
+            return Err("--resume only works with plain, unordered text output".to_string());
+        }
+
+        if opts.summary || opts.letters_report || opts.histogram || opts.by_length {
+            if opts.format != Format::Text {
+                return Err("reports only apply to text output".to_string());
+            }
+            if opts.resume.is_some() {
+                return Err("reports can't be combined with --resume".to_string());
+            }
+        }
+
+        if !opts.explain.is_empty()
+            && (opts.batch.is_some() || opts.resume.is_some() || opts.benchmark_dictionaries)
+        {
+            return Err(
+                "--explain can't be combined with --batch, --resume or --benchmark-dictionaries"
+                    .to_string(),
+            );
+        }
+
+        if opts.validate_dictionary
+            && (opts.batch.is_some()
+                || opts.resume.is_some()
+                || opts.benchmark_dictionaries
+                || !opts.explain.is_empty())
+        {
+            return Err(
+                "--validate-dictionary can't be combined with --batch, --resume, --benchmark-dictionaries or --explain"
+                    .to_string(),
+            );
+        }
+
+        if opts.shortlist.is_some() {
+            if !opts.paths.is_empty() {
+                return Err(
+                    "--shortlist replaces the dictionary, so can't be given one".to_string()
+                );
+            }
+            if opts.benchmark_dictionaries || opts.compare {
+                return Err(
+                    "--shortlist can't be combined with --benchmark-dictionaries or --compare"
+                        .to_string(),
+                );
+            }
+        }
+
+        if let Some(coverage) = opts.min_coverage {
+            if coverage == 0 || coverage > opts.word_len * opts.set_size {
+                return Err(format!(
+                    "--min-coverage must be between 1 and {} letters (the word length times the set size)",
+                    opts.word_len * opts.set_size
+                ));
+            }
+            if opts.batch.is_some() || opts.resume.is_some() {
+                return Err("--min-coverage can't be combined with --batch or --resume".to_string());
+            }
+        }
+
+        // --coverage: the letters a set of --group-size words covers,
+        // exactly or at least. Exactly as many as the words have between
+        // them is the disjoint puzzle, which its own search is far
+        // faster at; anything else lets the words share letters.
+        //
+        if let Some(coverage) = opts.coverage {
+            if opts.min_unique.is_some() {
+                return Err("--coverage can't be combined with --min-unique".to_string());
+            }
+
+            let disjoint = opts.max_len.is_none() && coverage == opts.word_len * opts.set_size;
+
+            match opts.coverage_mode.unwrap_or_default() {
+                CoverageMode::Exact if disjoint => {}
+                CoverageMode::Exact => {
+                    opts.min_unique = Some(coverage);
+                    opts.max_unique = Some(coverage);
+                }
+                CoverageMode::AtLeast => opts.min_unique = Some(coverage),
+            }
+        } else if opts.coverage_mode.is_some() {
+            return Err("--coverage-mode only applies with --coverage".to_string());
+        }
+
+        // (sets of words sharing letters are neither near-misses of a
+        // disjoint set, nor arranged into squares)
+        //
+        if opts.min_unique.is_some() && (opts.min_coverage.is_some() || opts.word_square) {
+            return Err(
+                "--min-unique can't be combined with --min-coverage or --word-square".to_string(),
+            );
+        }
+
+        if opts.retry_without_skip
+            && (opts.no_skip
+                || opts.min_coverage.is_some()
+                || opts.min_unique.is_some()
+                || opts.word_square
+                || opts.resume.is_some())
+        {
+            return Err("--retry-without-skip can't be combined with --no-skip, --min-coverage, --min-unique, --coverage, --word-square or --resume".to_string());
+        }
+
+        if opts.contains.as_deref() == Some("") {
+            return Err("--require-contains-substring needs something to look for".to_string());
+        }
+
+        if opts.tokens.is_some() {
+            if opts.allowed.is_some()
+                || opts.required.is_some()
+                || opts.excluded.is_some()
+                || opts.disjoint.is_some()
+                || opts.require_vowel
+                || opts.y_vowel
+                || !opts.forbidden.is_empty()
+            {
+                return Err(
+                    "--tokens can't be combined with --allowed, --required, --exclude, --disjoint, --require-vowel, --y-vowel or --forbid-bigram, which name letters"
+                        .to_string(),
+                );
+            }
+            if opts.format == Format::Binary || opts.casing != Casing::Lower {
+                return Err(
+                    "--tokens can't be combined with --format binary or --normalize-output"
+                        .to_string(),
+                );
+            }
+            if opts.grid || opts.word_square {
+                return Err("--tokens can't be combined with --grid or --word-square".to_string());
+            }
+        }
+
+        if opts.casing != Casing::Lower && opts.format == Format::Binary {
+            return Err("binary records always hold lowercase letters".to_string());
+        }
+
+        if opts.word_square {
+            if opts.format != Format::Text {
+                return Err("--word-square only supports text output".to_string());
+            }
+            if opts.batch.is_some()
+                || opts.resume.is_some()
+                || opts.compare
+                || opts.min_coverage.is_some()
+                || opts.sqlite.is_some()
+            {
+                return Err(
+                    "--word-square can't be combined with --batch, --resume, --compare, --min-coverage or --sqlite"
+                        .to_string(),
+                );
+            }
+            if opts.template.is_some() || opts.grid || opts.group_by_first || opts.unused_only {
+                return Err("--word-square prints each square as its rows".to_string());
+            }
+        }
+
+        if opts.sqlite.is_some() {
+            if opts.format != Format::Text {
+                return Err("--sqlite writes the solutions instead of any --format".to_string());
+            }
+            if opts.batch.is_some() || opts.resume.is_some() || opts.compare {
+                return Err(
+                    "--sqlite can't be combined with --batch, --resume or --compare".to_string(),
+                );
+            }
+        }
+
+        if opts.max_spellings.is_some() && opts.dedup != Dedup::Original {
+            return Err(
+                "--max-anagrams-per-slot only applies with --dedup-by original".to_string(),
+            );
+        }
+        if opts.anagram != Anagram::First && opts.dedup != Dedup::Bitmask {
+            return Err(
+                "--select-anagram only applies with --dedup-by bitmask, which shows one spelling"
+                    .to_string(),
+            );
+        }
+
+        if opts.format == Format::Ids {
+            if opts.id_table.is_none() {
+                return Err("--format ids needs an --id-table to write the words to".to_string());
+            }
+            if opts.show_masks {
+                return Err("--show-masks doesn't apply to --format ids".to_string());
+            }
+        } else if opts.id_table.is_some() {
+            return Err("--id-table only applies with --format ids".to_string());
+        }
+
+        // (the events of several threads would interleave)
+        //
+        if opts.event_log.is_some() {
+            if opts.threads.is_some_and(|threads| threads > 1) {
+                return Err("--event-log needs a serial search, on --threads 1".to_string());
+            }
+            if opts.batch.is_some() || opts.benchmark_dictionaries || opts.compare {
+                return Err(
+                    "--event-log can't be combined with --batch, --benchmark-dictionaries or --compare"
+                        .to_string(),
+                );
+            }
+            opts.threads = Some(1);
+        }
+
+        // --canonical-output: the same bytes from every run over the same
+        // dictionary. Each solution is a set of letter sets, spelled
+        // alphabetically first, found by a serial search (so even a
+        // --limit stops at the same ones) and printed sorted; the bits
+        // the letters get, and so the order of each solution's words,
+        // already depend on nothing but the dictionary.
+        //
+        if opts.canonical {
+            if opts.threads.is_some_and(|threads| threads > 1) {
+                return Err("--canonical-output needs a serial search, on --threads 1".to_string());
+            }
+            if opts.time_budget.is_some() {
+                return Err(
+                    "--canonical-output can't be combined with --time-budget, which stops at a different point each run"
+                        .to_string(),
+                );
+            }
+            if opts.dedup != Dedup::Bitmask {
+                return Err(
+                    "--canonical-output shows one spelling a letter set, so needs --dedup-by bitmask"
+                        .to_string(),
+                );
+            }
+            opts.threads = Some(1);
+            opts.order = Order::Alphabetical;
+            opts.anagram = Anagram::Alpha;
+        }
+
+        // (the counts make the dictionary unreadable as a plain list)
+        //
+        if opts.top_words.is_some()
+            && (opts.strict
+                || opts.dict_info
+                || opts.dry_parse
+                || opts.compare
+                || opts.benchmark_dictionaries
+                || opts.tokens.is_some())
+        {
+            return Err(
+                "--top-words can't be combined with --strict, --dict-info, --dry-parse, --compare, --benchmark-dictionaries or --tokens"
+                    .to_string(),
+            );
+        }
+
+        // (each solution flushed the moment the one search thread finds
+        // it, so it only makes sense where they're printed as found)
+        //
+        if opts.stream_immediate {
+            if opts.threads != Some(1) {
+                return Err("--stream-immediate needs a serial search, on --threads 1".to_string());
+            }
+            if !opts.is_streaming() || opts.sqlite.is_some() {
+                return Err(
+                    "--stream-immediate needs solutions printed as they're found, so no --order, --format json, grouping or --sqlite"
+                        .to_string(),
+                );
+            }
+        }
+
+        if opts.mmap
+            && (opts.tokens.is_some()
+                || opts.top_words.is_some()
+                || opts.shortlist.is_some()
+                || opts.paths.iter().any(|path| is_url(path)))
+        {
+            return Err(
+                "--mmap maps a plain file, so can't be combined with --tokens, --top-words, --shortlist or a URL"
+                    .to_string(),
+            );
+        }
+        #[cfg(feature = "encoding")]
+        if opts.mmap && opts.encoding.is_some() {
+            return Err("--mmap reads UTF-8, so can't be combined with --encoding".to_string());
+        }
+
+        if opts.output_limit.is_some() && opts.sqlite.is_some() {
+            return Err(
+                "--output-limit-bytes limits what's printed, not what --sqlite writes".to_string(),
+            );
+        }
+        // (these print reports, rather than solutions, which the limit
+        // doesn't know how to cut short)
+        //
+        if opts.output_limit.is_some()
+            && (opts.self_test
+                || opts.validate_dictionary
+                || opts.dict_info
+                || opts.dry_parse
+                || opts.benchmark_dictionaries
+                || opts.compare
+                || opts.profile_buckets
+                || opts.graph_stats
+                || opts.suggest
+                || opts.letter_pairs.is_some()
+                || !opts.explain.is_empty()
+                || opts.summary
+                || opts.letters_report
+                || opts.histogram
+                || opts.by_length)
+        {
+            return Err(
+                "--output-limit-bytes only limits solutions, so can't be combined with --self-test, --validate-dictionary, --dict-info, --dry-parse, --benchmark-dictionaries, --compare, --profile-buckets, --emit-graph-stats, --suggest, --letter-pairs, --explain, --summary, --letters-used-report, --histogram-solutions or --count-by-length"
+                    .to_string(),
+            );
+        }
+
+        if opts.letter_pairs == Some(0) {
+            return Err("--letter-pairs needs at least 1 letter".to_string());
+        }
+
+        if let Some(c) = opts.used.chars().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(format!("--used takes letters, not {:?}", c));
+        }
+        if !opts.used.is_empty() && !opts.suggest {
+            return Err("--used only applies to --suggest".to_string());
+        }
+
+        // (a guess is one word, so however few letters are left there's
+        // always a set to fill)
+        //
+        if opts.suggest {
+            opts.set_size = 1;
+        }
+
+        if (opts.self_test
+            || opts.letter_pairs.is_some()
+            || opts.profile_buckets
+            || opts.graph_stats
+            || opts.dump_index.is_some()
+            || opts.suggest)
+            && (opts.batch.is_some()
+                || opts.resume.is_some()
+                || opts.benchmark_dictionaries
+                || opts.compare
+                || opts.dict_info
+                || opts.validate_dictionary
+                || !opts.explain.is_empty())
+        {
+            return Err(
+                "--self-test, --letter-pairs, --profile-buckets, --emit-graph-stats, --dump-index and --suggest can't be combined with --batch, --resume, --benchmark-dictionaries, --compare, --dict-info, --validate-dictionary or --explain"
+                    .to_string(),
+            );
+        }
+
+        if opts.compare {
+            if opts.batch.is_some()
+                || opts.resume.is_some()
+                || opts.benchmark_dictionaries
+                || opts.dict_info
+                || opts.validate_dictionary
+                || !opts.explain.is_empty()
+            {
+                return Err(
+                    "--compare can't be combined with --batch, --resume, --benchmark-dictionaries, --dict-info, --validate-dictionary or --explain"
+                        .to_string(),
+                );
+            }
+            if opts.limit.is_some() || opts.per_letter_limit.is_some() {
+                return Err("--compare needs every solution, so can't be limited".to_string());
+            }
+            if opts.format != Format::Text {
+                return Err("--compare only supports text output".to_string());
+            }
+            if opts.paths.len() != 2 {
+                return Err("--compare needs exactly two dictionaries, old then new".to_string());
+            }
+        }
+
+        if opts.batch.is_some() {
+            if opts.resume.is_some() || opts.benchmark_dictionaries {
+                return Err(
+                    "--batch can't be combined with --resume or --benchmark-dictionaries"
+                        .to_string(),
+                );
+            }
+            if opts.format != Format::Text {
+                return Err("--batch only works with text output".to_string());
+            }
+        } else if opts.batch_dedup {
+            return Err("--batch-dedup only applies to a --batch".to_string());
+        }
+
+        Ok(opts)
+    }
+
+    // the length to suggest instead of a --len the dictionary has no
+    // words of
+    //
+    fn suggested_len(&self, dict: &PreparedDict) -> Option<usize> {
+        self.len_given
+            .then(|| dominant_length(dict, self.lengths()))
+            .flatten()
+    }
+
+    fn lengths(&self) -> RangeInclusive<usize> {
+        self.word_len..=self.max_len.unwrap_or(self.word_len)
+    }
+
+    // solutions are streamed straight to stdout unless we need to see
+    // all of them before printing (ordering, grouping, or a JSON
+    // envelope).
+    //
+    fn is_streaming(&self) -> bool {
+        matches!(self.format, Format::Text | Format::Binary | Format::Ids)
+            && self.order == Order::Found
+            && self.per_letter_limit.is_none()
+            && !self.group_by_first
+    }
+
+    // whether to print `solution`: always, unless --batch-dedup has
+    // seen an earlier query print it
+    //
+    fn is_new(&self, solution: &Solution) -> bool {
+        self.shown
+            .as_ref()
+            .is_none_or(|shown| shown.first(solution, self.query))
+    }
+
+    fn speller(&self) -> Speller<'_> {
+        Speller {
+            casing: self.casing,
+            spellings: &self.spellings,
+            tokens: self.tokens.as_ref(),
+        }
+    }
+
+    // parse the command line, and with --use, parse it again on top of
+    // the named manifest entry's options (so the command line wins),
+    // returning the options actually in force
+    //
+    fn with_preset(args: Vec<String>) -> Result<(Self, Vec<String>), String> {
+        let opts = Options::from_args(args.iter().cloned())?;
+
+        let name = match &opts.preset {
+            Some(name) => name,
+            None => return Ok((opts, args)),
+        };
+
+        let manifest = opts.manifest.as_deref().unwrap_or(DEFAULT_MANIFEST);
+        let preset = manifest::find(manifest, name)?;
+
+        let args: Vec<String> = preset.args.into_iter().chain(args).collect();
+        let mut opts = Options::from_args(args.iter().cloned())?;
+
+        if opts.paths.is_empty() {
+            opts.paths.push(preset.path);
+        }
+
+        Ok((opts, args))
+    }
+
+    // everything that changes which solutions a run finds, or how they
+    // are printed; a checkpoint is only valid for the same fingerprint
+    //
+    fn fingerprint(&self) -> String {
+        // (the --freq-source corpus by its contents, as they change the
+        // order the solutions are found in)
+        //
+        let freq = self.freq_source.as_ref().map(|path| {
+            let corpus = self.read_words(path).unwrap_or_default();
+
+            format!("{:016x}", fnv1a(FNV1A_BASIS, corpus.as_bytes()))
+        });
+
+        let fingerprint = format!(
+            "len={} count={} skip={} allowed={} required={} excluded={} disjoint={} limit={:?} intersect={} anagrammable={} vowel={} y_vowel={} dedup={:?} spellings={:?} unused={} unused_only={} masks={} grid={} template={:?} case={:?} tokens={:?} contains={:?} max_len={:?} forbidden={:?} min_unique={:?} anagram={:?} max_unique={:?} include={:?} freq={:?}",
+            self.word_len,
+            self.set_size,
+            !self.no_skip,
+            self.allowed.as_deref().unwrap_or("*"),
+            self.required.as_deref().unwrap_or(""),
+            self.excluded.as_deref().unwrap_or(""),
+            self.disjoint.as_deref().unwrap_or(""),
+            self.limit,
+            self.intersect.as_deref().unwrap_or("-"),
+            self.require_anagrammable,
+            self.require_vowel,
+            self.y_vowel,
+            self.dedup,
+            self.max_spellings,
+            self.show_unused,
+            self.unused_only,
+            self.show_masks,
+            self.grid,
+            self.template.as_ref().map(Template::source),
+            self.casing,
+            self.tokens.as_ref().map(Tokens::source),
+            self.contains,
+            self.max_len,
+            self.forbidden,
+            self.min_unique,
+            self.anagram,
+            self.max_unique,
+            self.must_include.as_ref().map(|word| word.to_lowercase()),
+            freq,
+        );
+
+        #[cfg(feature = "unicode")]
+        let fingerprint = format!("{} normalize={:?}", fingerprint, self.normalize);
+
+        fingerprint
+    }
+
+    // map the command line onto the library's solver
+    //
+    fn builder(&self) -> SolverBuilder {
+        let mut builder = SolverBuilder::new()
+            .word_len_range(self.lengths())
+            .set_size(self.set_size)
+            .allow_skip(!self.no_skip)
+            .require_anagrammable(self.require_anagrammable)
+            .require_vowel(self.require_vowel)
+            .y_vowel(self.y_vowel)
+            .order(self.order)
+            .seed_order(self.seed_order)
+            .backend(self.backend)
+            .dedup_by(self.dedup)
+            .select_anagram(self.anagram)
+            .cancel_flag(interrupt::cancel_flag());
+
+        #[cfg(feature = "unicode")]
+        {
+            builder = builder.normalize(self.normalize);
+        }
+
+        #[cfg(feature = "progress")]
+        if self.progress_bar {
+            builder = progress_bar::attach(builder);
+        }
+
+        if let Some(letters) = &self.allowed {
+            builder = builder.allowed_letters(letters);
+        }
+        if let Some(tokens) = &self.tokens {
+            builder = builder.allowed_letters(&tokens.letters());
+        }
+        if let Some(log) = &self.events {
+            let (log, tokens) = (log.clone(), self.tokens.clone());
+
+            builder = builder.event_log(move |event| {
+                let _ = writeln!(log.lock().unwrap(), "{}", EventJson(event, tokens.as_ref()));
+            });
+        }
+
+        // a word of each solution has to contain the substring, as it's
+        // spelled in the solution: where anagrams are collapsed, that's
+        // the dictionary's first spelling of its letters, so another
+        // spelling containing it doesn't count (--dedup-by original
+        // checks every spelling)
+        //
+        if let Some(part) = &self.contains {
+            let part = part.clone();
+            let tokens = self.tokens.clone();
+
+            builder = builder.filter(move |solution| {
+                solution.words().iter().any(|word| {
+                    let spelled = match &tokens {
+                        Some(tokens) => tokens.spell(word).to_string(),
+                        None => word.to_string(),
+                    };
+                    spelled.contains(&part)
+                })
+            });
+        }
+        for bigram in &self.forbidden {
+            builder = builder.forbid_bigram(bigram);
+        }
+        if let Some(letters) = &self.required {
+            builder = builder.required_letters(letters);
+        }
+        if let Some(letters) = &self.excluded {
+            builder = builder.excluded_letters(letters);
+        }
+        if let Some(letters) = &self.disjoint {
+            builder = builder.disjoint_from(letters);
+        }
+        if let Some(word) = &self.must_include {
+            builder = builder.must_include(word);
+        }
+        if let Some(limit) = self.limit {
+            builder = builder.limit(limit);
+        }
+        if let Some(limit) = self.per_letter_limit {
+            builder = builder.per_letter_limit(limit);
+        }
+        if let Some(min) = self.min_unique {
+            builder = match self.max_unique {
+                Some(max) => builder.unique_range(min..=max),
+                None => builder.min_unique(min),
+            };
+        }
+        if let Some(max) = self.max_spellings {
+            builder = builder.max_spellings(max);
+        }
+        if let Some(depth) = self.trace_depth {
+            builder = builder.max_trace_depth(depth);
+        }
+        if let Some(threads) = self.threads {
+            builder = builder.threads(threads);
+        }
+        if self.warm_pool {
+            builder = builder.thread_pool(warm_pool(self.threads));
+        }
+        if let Some(budget) = self.time_budget {
+            builder = builder.time_budget(budget);
+        }
+
+        // the optional second word list, which every candidate must also
+        // appear in
+        //
+        if let Some(path) = &self.intersect {
+            let words = match self.read_words(path) {
+                Ok(words) => words,
+                Err(err) => {
+                    eprintln!("error: can't read word list '{}': {}", path, err);
+                    exit(2);
+                }
+            };
+
+            builder = builder.intersect(words.lines().map(str::trim));
+        }
+
+        // a text to take letter frequencies from, which only changes
+        // the order the search tries letters in
+        //
+        if let Some(path) = &self.freq_source {
+            let corpus = match self.read_words(path) {
+                Ok(corpus) => corpus,
+                Err(err) => {
+                    eprintln!("error: can't read corpus '{}': {}", path, err);
+                    exit(2);
+                }
+            };
+
+            builder = builder.letter_frequencies(&corpus);
+        }
+
+        builder
+    }
+
+    // read a word list, transcoding it to UTF-8 first if it's in some
+    // other --encoding
+    //
+    fn read_words(&self, path: &str) -> Result<String, String> {
+        let text = self.read_text(path)?;
+
+        match &self.tokens {
+            Some(tokens) => Ok(tokens.encode(&text)),
+            None => Ok(text),
+        }
+    }
+
+    // the dictionary's text, before any --tokens are split out of it
+    //
+    fn read_text(&self, path: &str) -> Result<String, String> {
+        if let Some(words) = &self.shortlist {
+            return Ok(words.clone());
+        }
+
+        if is_url(path) {
+            let bytes = fetch(path)?;
+
+            #[cfg(feature = "encoding")]
+            if let Some(encoding) = self.encoding {
+                return Ok(encoding.decode(&bytes).0.into_owned());
+            }
+
+            return String::from_utf8(bytes)
+                .map_err(|_| "it isn't valid UTF-8 (see --encoding)".to_string());
+        }
+
+        #[cfg(feature = "encoding")]
+        if let Some(encoding) = self.encoding {
+            let bytes = fs::read(path).map_err(|err| err.to_string())?;
+
+            return Ok(encoding.decode(&bytes).0.into_owned());
+        }
+
+        fs::read_to_string(path).map_err(|err| match err.kind() {
+            io::ErrorKind::InvalidData => format!("{} (see --encoding)", err),
+            _ => err.to_string(),
+        })
+    }
+
+    // prepare a dictionary a line at a time, also returning the hash of
+    // its text (as --json and --resume record it). Only a dictionary in
+    // some other --encoding is read whole, to transcode it first (and a
+    // URL is fetched whole, a --shortlist is whole already, and words
+    // split into --tokens are hashed as their stand-in letters).
+    //
+    fn stream_words(
+        &self,
+        solver: &Solver,
+        path: &str,
+    ) -> Result<(PreparedDict<'static>, u64), String> {
+        let whole = self.shortlist.is_some()
+            || self.tokens.is_some()
+            || self.top_words.is_some()
+            || is_url(path);
+
+        #[cfg(feature = "mmap")]
+        if self.mmap && !whole {
+            return map_words(solver, path);
+        }
+
+        #[cfg(feature = "encoding")]
+        let whole = whole || self.encoding.is_some();
+
+        if whole {
+            let mut text = self.read_words(path)?;
+
+            if let Some(n) = self.top_words {
+                text = top_words::keep(solver, &text, n)?;
+            }
+            let mut reader = io::BufReader::new(Fnv1aReader::new(text.as_bytes()));
+            let dict = solver
+                .prepare_reader(&mut reader)
+                .map_err(|err| err.to_string())?;
+
+            return Ok((dict, reader.into_inner().hash));
+        }
+
+        let file = fs::File::open(path).map_err(|err| err.to_string())?;
+        let mut reader = io::BufReader::new(Fnv1aReader::new(file));
+        let dict = solver
+            .prepare_reader(&mut reader)
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::InvalidData => format!("{} (see --encoding)", err),
+                _ => err.to_string(),
+            })?;
+
+        Ok((dict, reader.into_inner().hash))
+    }
+}
+
+// --mmap: prepare a dictionary straight from the file mapped into
+// memory, so (unlike reading it a line at a time) nothing is copied but
+// the usable words: the rest are only ever pages of the OS's cache. The
+// map is kept for the rest of the run, as the words point into it.
+//
+#[cfg(feature = "mmap")]
+fn map_words(solver: &Solver, path: &str) -> Result<(PreparedDict<'static>, u64), String> {
+    let file = fs::File::open(path).map_err(|err| err.to_string())?;
+
+    // (the file mustn't change under us while it's mapped, which is
+    // as much as any reader of it can hope for)
+    //
+    let map = unsafe { memmap2::Mmap::map(&file) }.map_err(|err| err.to_string())?;
+    let map: &'static [u8] = Box::leak(Box::new(map));
+
+    let dict = solver
+        .prepare_bytes(map)
+        .map_err(|err| format!("{} (see --encoding)", err))?;
+
+    Ok((dict, fnv1a(FNV1A_BASIS, map)))
+}
+
+// whether `word` (or an anagram of it) is one of the dictionary's
+// candidates
+//
+fn is_candidate(solver: &Solver, dict: &PreparedDict, word: &str) -> bool {
+    let letters = |word: &ZWord| word.iter().fold(0u64, |acc, z| acc | 1 << z.ord());
+
+    let Ok(word) = solver.parse_word(word) else {
+        return false;
+    };
+
+    solver
+        .candidates(dict)
+        .iter()
+        .any(|candidate| letters(candidate) == letters(&word))
+}
+
+// a dictionary "path" that's to be downloaded instead
+//
+fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+// download a dictionary (following any redirects), failing on
+// anything but a successful response
+//
+#[cfg(feature = "net")]
+fn fetch(url: &str) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let response = ureq::get(url).call().map_err(|err| match err {
+        ureq::Error::Status(status, response) => {
+            format!("the server answered {} {}", status, response.status_text())
+        }
+        ureq::Error::Transport(err) => format!("can't fetch it: {}", err),
+    })?;
+
+    let mut body = Vec::new();
+
+    response
+        .into_reader()
+        .read_to_end(&mut body)
+        .map_err(|err| format!("the download failed: {}", err))?;
+
+    Ok(body)
+}
+
+#[cfg(not(feature = "net"))]
+fn fetch(_url: &str) -> Result<Vec<u8>, String> {
+    Err("reading a dictionary from a URL needs a build with the 'net' feature".to_string())
+}
+
+// a length of time, as a number and a unit ("ms", "s" or "m"; plain
+// seconds without one), as in "2s" or "1.5m"
+//
+fn duration(text: &str) -> Option<Duration> {
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        _ => return None,
+    };
+
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+// how often to write the checkpoint out: a number of seeds, as in
+// "4seeds", or a length of time
+//
+fn checkpoint_interval(text: &str) -> Option<Interval> {
+    match text.strip_suffix("seeds") {
+        Some(seeds) => match seeds.parse() {
+            Ok(0) | Err(_) => None,
+            Ok(seeds) => Some(Interval::Seeds(seeds)),
+        },
+        None => duration(text).map(Interval::Time),
+    }
+}
+
+// the word lengths from one to another, as in "4..6" (both included;
+// "5..5" is just --len 5)
+//
+fn length_range(range: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("invalid length range '{}' (expected e.g. '4..6')", range);
+
+    let (min, max) = range.split_once("..").ok_or_else(invalid)?;
+    let (min, max): (usize, usize) = match (min.parse(), max.parse()) {
+        (Ok(min), Ok(max)) => (min, max),
+        _ => return Err(invalid()),
+    };
+
+    if min > max {
+        return Err(format!(
+            "length range '{}' runs backwards ({} is more than {})",
+            range, min, max
+        ));
+    }
+
+    Ok((min, max))
+}
+
+// every letter from one to another, as in "a-m"
+//
+fn letter_range(range: &str) -> Result<String, String> {
+    let invalid = || format!("invalid letter range '{}' (expected e.g. 'a-m')", range);
+
+    let (start, end) = match range.chars().collect::<Vec<_>>()[..] {
+        [start, '-', end] if start.is_ascii_alphabetic() && end.is_ascii_alphabetic() => {
+            (start.to_ascii_lowercase(), end.to_ascii_lowercase())
+        }
+        _ => return Err(invalid()),
+    };
+
+    if start > end {
+        return Err(format!(
+            "letter range '{}' runs backwards ('{}' comes after '{}')",
+            range, start, end
+        ));
+    }
+
+    Ok((start..=end).collect())
+}
+
+// the letters of each --locale's alphabet
+//
+const LOCALES: &[(&str, &str)] = &[
+    // English, and English without its four rarest letters
+    //
+    ("en", "abcdefghijklmnopqrstuvwxyz"),
+    ("en-common", "abcdefghiklmnoprstuvwy"),
+    // Italian, which only borrows j, k, w, x and y
+    //
+    ("it", "abcdefghilmnopqrstuvz"),
+    // classical Latin, before j, u and w were told apart from i and v
+    //
+    ("la", "abcdefghiklmnopqrstvxyz"),
+];
+
+fn locale(name: &str) -> Result<&'static str, String> {
+    match LOCALES
+        .iter()
+        .find(|(locale, _)| locale.eq_ignore_ascii_case(name))
+    {
+        Some((_, letters)) => Ok(letters),
+        None => Err(format!(
+            "unknown locale '{}' (expected one of {})",
+            name,
+            LOCALES
+                .iter()
+                .map(|(locale, _)| *locale)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing value for '{}'", flag))?;
+
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{}' for '{}'", value, flag))
+}
+
+// lists the candidate words no solution uses, for curating a
+// dictionary. This needs every solution, so is as slow as a full solve
+// (whatever --limit says).
+//
+fn validate_dictionary(solver: &Solver, dict: &PreparedDict) {
+    eprintln!("warning: --validate-dictionary solves the whole dictionary, which may take a while");
+
+    let candidates = solver.candidates(dict).len();
+    let unsolvable = solver.unsolvable(dict);
+
+    println!(
+        "{} of {} candidate words are in no solution:",
+        unsolvable.len(),
+        candidates
+    );
+    for word in unsolvable {
+        println!("    {}", word);
+    }
+}
+
+// the search's buckets, biggest first: a letter's bucket holds the
+// words whose rarest letter it is, which are the words the search
+// tries whenever that letter is the lowest one still free
+//
+fn profile_buckets(solver: &Solver, dict: &PreparedDict) {
+    let mut buckets: Vec<_> = solver.bucket_sizes(dict).into_iter().enumerate().collect();
+    let total: usize = buckets.iter().map(|(_, (_, size))| size).sum();
+
+    buckets.sort_by_key(|&(bit, (_, size))| (std::cmp::Reverse(size), bit));
+
+    println!("== buckets by size ({} candidates) ==", total);
+    println!("  bit  letter   words   share");
+    for (bit, (letter, size)) in buckets {
+        println!(
+            "  {:>3}  {:>6}  {:>6}  {:>5.1}%",
+            bit,
+            letter.chr(),
+            size,
+            100.0 * size as f64 / total.max(1) as f64
+        );
+    }
+}
+
+// --suggest: the candidates that would try the most letters not yet
+// --used (as a next Wordle guess), ties going to those whose new letters
+// more of the candidates share, so the guess says the most about them
+//
+fn suggest(solver: &Solver, dict: &PreparedDict, opts: &Options) {
+    let used = opts.used.chars().fold(0u64, |acc, c| {
+        acc | 1 << (c.to_ascii_lowercase() as u8 - b'a')
+    });
+
+    let candidates = solver.candidates(dict);
+    let mut sharing = [0usize; MAX_SYMBOLS];
+
+    for word in &candidates {
+        for z in word.iter() {
+            sharing[z.ord()] += 1;
+        }
+    }
+
+    let mut scored: Vec<_> = candidates
+        .iter()
+        .map(|word| {
+            let new: Vec<usize> = word
+                .iter()
+                .map(|z| z.ord())
+                .filter(|&i| used & 1 << i == 0)
+                .collect();
+            let shared: usize = new.iter().map(|&i| sharing[i]).sum();
+
+            (new.len(), shared, word)
+        })
+        .collect();
+
+    scored.sort_by_key(|&(new, shared, _)| std::cmp::Reverse((new, shared)));
+
+    if scored.is_empty() {
+        println!("no candidate words fit these constraints");
+        return;
+    }
+
+    println!("== best guesses ({} candidates) ==", candidates.len());
+    println!("  new letters  word");
+    for (new, _, word) in scored.iter().take(SUGGESTIONS) {
+        println!("  {:>11}  {}", new, opts.speller().spell(word));
+    }
+}
+
+// --dump-index: the search's index of the dictionary, as JSON for
+// reading (or a script) rather than the search: which bit each letter
+// was given (rarest first), the letter each bit stands for, and how
+// many candidates each bit's bucket holds
+//
+fn dump_index(solver: &Solver, dict: &PreparedDict, path: &str) {
+    let buckets = solver.bucket_sizes(dict);
+
+    let mut bits = vec![0; buckets.len()];
+    for (bit, (letter, _)) in buckets.iter().enumerate() {
+        bits[letter.ord()] = bit;
+    }
+
+    let mut json = String::new();
+
+    json.push_str("{\n");
+    json.push_str(&format!(
+        "  \"candidates\": {},\n",
+        solver.candidates(dict).len()
+    ));
+    json.push_str("  \"letters\": {\n");
+    for (i, bit) in bits.iter().enumerate() {
+        let comma = if i + 1 < bits.len() { "," } else { "" };
+        json.push_str(&format!(
+            "    \"{}\": {}{}\n",
+            ZChar::from_ord(i).unwrap().chr(),
+            bit,
+            comma
+        ));
+    }
+    json.push_str("  },\n");
+    json.push_str("  \"buckets\": [\n");
+    for (bit, (letter, size)) in buckets.iter().enumerate() {
+        let comma = if bit + 1 < buckets.len() { "," } else { "" };
+        json.push_str(&format!(
+            "    {{ \"bit\": {}, \"letter\": \"{}\", \"words\": {} }}{}\n",
+            bit,
+            letter.chr(),
+            size,
+            comma
+        ));
+    }
+    json.push_str("  ]\n");
+    json.push_str("}\n");
+
+    if let Err(err) = fs::write(path, json) {
+        eprintln!("error: can't write the index to '{}': {}", path, err);
+        exit(2);
+    }
+}
+
+// --format ids: number every word a solution could use, in sorted
+// order so the numbers only change when the candidates do, and write
+// the numbers and words to `path` (a tab-separated line each) for the
+// solutions to be read back with
+//
+fn write_id_table(
+    solver: &Solver,
+    dict: &PreparedDict,
+    path: &str,
+    opts: &Options,
+) -> HashMap<String, usize> {
+    let candidates = solver.candidates(dict);
+
+    // keeping every spelling, solutions can show any of them
+    //
+    let mut words: Vec<ZWord> = match opts.dedup {
+        Dedup::Bitmask => candidates,
+        Dedup::Original => {
+            let letters = |word: &ZWord| word.iter().fold(0u64, |acc, z| acc | 1 << z.ord());
+            let kept: HashSet<u64> = candidates.iter().map(letters).collect();
+
+            dict.words()
+                .filter(|word| kept.contains(&letters(word)))
+                .copied()
+                .collect()
+        }
+    };
+
+    words.sort_by_cached_key(ZWord::to_string);
+    words.dedup_by(|a, b| a.iter().eq(b.iter()));
+
+    let mut table = String::new();
+
+    for (id, word) in words.iter().enumerate() {
+        table.push_str(&format!("{}\t{}\n", id, opts.speller().spell(word)));
+    }
+
+    if let Err(err) = fs::write(path, table) {
+        eprintln!("error: can't write id table '{}': {}", path, err);
+        exit(2);
+    }
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(id, word)| (word.to_string(), id))
+        .collect()
+}
+
+// --dry-parse: the words the solver reads from the dictionary, one per
+// line as it sees them (trimmed, normalized, lowercase, or split into
+// --tokens), without picking out any of them by length or solving.
+// The lines it can't read are left out, but counted on stderr.
+//
+fn dry_parse(solver: &Solver, opts: &Options) {
+    let path = &opts.paths[0];
+    let text = match opts.read_text(path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: can't read dictionary '{}': {}", path, err);
+            exit(2);
+        }
+    };
+
+    let (mut accepted, mut blank) = (0, 0);
+    let mut rejected: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            blank += 1;
+            continue;
+        }
+
+        let letters = match &opts.tokens {
+            Some(tokens) => match tokens.encode_word(line) {
+                Some(letters) => Cow::Owned(letters),
+                None => {
+                    *rejected.entry("don't split into tokens").or_default() += 1;
+                    continue;
+                }
+            },
+            None => Cow::Borrowed(line),
+        };
+
+        match solver.parse_word(&letters) {
+            Ok(word) => {
+                accepted += 1;
+                println!("{}", opts.speller().spell(&word));
+            }
+            Err(Problem::NotALetter(_)) => {
+                *rejected
+                    .entry("have something other than letters")
+                    .or_default() += 1
+            }
+            Err(_) => *rejected.entry("repeat a letter").or_default() += 1,
+        }
+    }
+
+    eprintln!(
+        "{} word{} read from {} ({} lines rejected, {} blank)",
+        accepted,
+        if accepted == 1 { "" } else { "s" },
+        path,
+        rejected.values().sum::<usize>(),
+        blank
+    );
+    for (why, count) in rejected {
+        eprintln!("  {:>8} {}", count, why);
+    }
+}
+
+// how many of the best guesses --suggest lists
+//
+const SUGGESTIONS: usize = 10;
+
+// past this many candidates, testing every pair of them takes a while,
+// so only the degrees of an evenly spread sample are found (each still
+// against every candidate)
+//
+const GRAPH_SAMPLE: usize = 20_000;
+
+// the graph with a node per candidate, and an edge between any two
+// that share no letters: how many others each word could sit beside in
+// a solution, which says a lot about how many solutions there will be
+//
+fn graph_stats(solver: &Solver, dict: &PreparedDict) {
+    let words = solver.candidates(dict);
+    let masks: Vec<u64> = words
+        .iter()
+        .map(|word| word.iter().fold(0u64, |acc, z| acc | 1 << z.ord()))
+        .collect();
+
+    let step = masks.len().div_ceil(GRAPH_SAMPLE).max(1);
+    let mut degrees: Vec<(usize, usize)> = (0..masks.len())
+        .step_by(step)
+        .map(|i| {
+            let degree = masks.iter().filter(|&&other| masks[i] & other == 0).count();
+
+            (degree, i)
+        })
+        .collect();
+
+    println!("== compatibility graph ({} candidates) ==", masks.len());
+
+    if degrees.is_empty() {
+        return;
+    }
+    if step > 1 {
+        println!(
+            "  (sampled: the degrees of {} of them, every {}th)",
+            degrees.len(),
+            step
+        );
+    }
+
+    degrees.sort_unstable();
+
+    let total: usize = degrees.iter().map(|&(degree, _)| degree).sum();
+    let mean = total as f64 / degrees.len() as f64;
+    let quantile = |q: f64| degrees[((degrees.len() - 1) as f64 * q).round() as usize].0;
+    let (max, busiest) = degrees[degrees.len() - 1];
+    let isolated = degrees
+        .iter()
+        .take_while(|&&(degree, _)| degree == 0)
+        .count();
+
+    println!(
+        "  edges   {:.0} (density {:.2}%)",
+        mean * masks.len() as f64 / 2.0,
+        100.0 * mean / (masks.len() - 1).max(1) as f64
+    );
+    println!("  degree  min     {}", degrees[0].0);
+    println!("          25%     {}", quantile(0.25));
+    println!("          median  {}", quantile(0.5));
+    println!("          75%     {}", quantile(0.75));
+    println!("          max     {} ({})", max, words[busiest]);
+    println!("          mean    {:.1}", mean);
+    println!(
+        "  isolated {} (sharing a letter with every other word)",
+        isolated
+    );
+}
+
+// checks the index the search would build for the dictionary, rather
+// than searching it, and exits with 1 if any invariant doesn't hold
+//
+fn self_test(solver: &Solver, dict: &PreparedDict, path: &str) {
+    println!(
+        "self-test: {} ({} candidates)",
+        path,
+        solver.candidates(dict).len()
+    );
+
+    let mut failed = false;
+
+    for check in solver.self_test(dict) {
+        match check.result {
+            Ok(()) => println!("  pass  {}", check.name),
+            Err(msg) => {
+                println!("  FAIL  {}: {}", check.name, msg);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
+
+// solves an old and a new version of a dictionary, and prints the
+// solutions each has that the other doesn't. Solutions are matched by
+// their letter sets (Solution::id), so a word merely respelled, or
+// picked in another order, doesn't count as a change.
+//
+fn compare_dictionaries(solver: &Solver, opts: &Options) {
+    let mut solved = Vec::new();
+
+    for path in &opts.paths {
+        let dict = match opts.stream_words(solver, path) {
+            Ok((dict, _)) => dict,
+            Err(err) => {
+                eprintln!("error: can't read dictionary '{}': {}", path, err);
+                exit(2);
+            }
+        };
+
+        let by_id: HashMap<u64, Solution> = solver
+            .solve_prepared(&dict)
+            .into_iter()
+            .map(|solution| (solution.id(), solution))
+            .collect();
+
+        // (each dictionary's solutions are printed as it spells them)
+        //
+        let opts = match opts.casing {
+            Casing::AsInput => Options {
+                spellings: Arc::new(spellings(&dict)),
+                ..opts.clone()
+            },
+            _ => opts.clone(),
+        };
+
+        solved.push((by_id, opts));
+    }
+
+    let (old, new) = (&solved[0], &solved[1]);
+
+    // the solutions in `a` but not `b`, in a stable order
+    //
+    let only = |(a, opts): &(HashMap<u64, Solution>, Options),
+                (b, _): &(HashMap<u64, Solution>, Options)| {
+        let mut only: Vec<String> = a
+            .iter()
+            .filter(|(id, _)| !b.contains_key(id))
+            .map(|(_, solution)| display(solution, opts).to_string())
+            .collect();
+
+        only.sort_unstable();
+        only
+    };
+
+    let removed = only(old, new);
+    let added = only(new, old);
+
+    println!(
+        "== removed: only in {} ({}) ==",
+        opts.paths[0],
+        removed.len()
+    );
+    for line in &removed {
+        println!("- {}", line);
+    }
+
+    println!();
+    println!("== added: only in {} ({}) ==", opts.paths[1], added.len());
+    for line in &added {
+        println!("+ {}", line);
+    }
+
+    println!();
+    println!(
+        "{} removed, {} added, {} in both",
+        removed.len(),
+        added.len(),
+        old.0.len() - removed.len()
+    );
+}
+
+// times the solver over each dictionary in turn and prints one row of
+// counts and per-phase timings per file.
+//
+fn benchmark_dictionaries(solver: &Solver, opts: &Options) {
+    println!(
+        "{:<32} {:>10} {:>10} {:>9} {:>10} {:>9}",
+        "dictionary", "candidates", "solutions", "index ms", "search ms", "total ms"
+    );
+
+    for path in &opts.paths {
+        let words = match opts.read_words(path) {
+            Ok(words) => words,
+            Err(err) => {
+                println!("{:<32} error: {}", path, err);
+                continue;
+            }
+        };
+
+        let stats = solver.solve_with(&words, |_| {});
+
+        println!(
+            "{:<32} {:>10} {:>10} {:>9} {:>10} {:>9}",
+            path,
+            stats.candidates,
+            stats.solutions,
+            stats.index_time.as_millis(),
+            stats.search_time.as_millis(),
+            (stats.index_time + stats.search_time).as_millis()
+        );
+    }
+}
+
+// a dictionary where the word length has almost no usable words is
+// more likely the wrong file (say a puzzle list of some other length),
+// or the wrong --len, than what was meant: so warn when the length the
+// dictionary has the most words of has over this many times as many
+// as the word lengths. A whole language's word list, whose lengths are
+// spread out, stays quiet at a length with a fair share.
+//
+const FEW_OF_LENGTH: usize = 10;
+
+fn warn_of_lengths(dict: &PreparedDict, lengths: RangeInclusive<usize>) {
+    let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+
+    for word in dict.words() {
+        *counts.entry(word.len()).or_default() += 1;
+    }
+
+    let of_length: usize = counts
+        .iter()
+        .filter(|(len, _)| lengths.contains(len))
+        .map(|(_, count)| count)
+        .sum();
+    let main = counts
+        .into_iter()
+        .max_by_key(|&(len, count)| (count, std::cmp::Reverse(len)));
+
+    let Some((main_len, most)) = main else {
+        return;
+    };
+    if most <= FEW_OF_LENGTH * of_length {
+        return;
+    }
+
+    eprintln!(
+        "warning: only {} of the dictionary's {} usable words have {} letters, against {} with {}; is this the right dictionary, and --len? (--no-warn hides this)",
+        of_length,
+        dict.len(),
+        describe_lengths(lengths),
+        most,
+        main_len
+    );
+}
+
+// when the dictionary has no usable words of the word lengths, but most
+// of its words have some other one length, that length (as most likely
+// the one meant)
+//
+fn dominant_length(dict: &PreparedDict, lengths: RangeInclusive<usize>) -> Option<usize> {
+    let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+
+    for word in dict.words() {
+        *counts.entry(word.len()).or_default() += 1;
+    }
+
+    if counts.keys().any(|len| lengths.contains(len)) {
+        return None;
+    }
+
+    counts
+        .into_iter()
+        .find(|&(_, count)| 2 * count > dict.len())
+        .map(|(len, _)| len)
+}
+
+fn describe_lengths(lengths: RangeInclusive<usize>) -> String {
+    match lengths.start() == lengths.end() {
+        true => lengths.start().to_string(),
+        false => format!("{} to {}", lengths.start(), lengths.end()),
+    }
+}
+
+// the solutions that can also be stacked into a word square, each
+// square printed as its rows (after a blank line), with its solution's
+// unused letters when asked for
+//
+fn run_word_squares(solver: &Solver, dict: &PreparedDict, opts: &Options) -> usize {
+    let squares = word_square::Squares::new(dict, opts.set_size);
+    let found = Mutex::new((0, 0));
+
+    let stats = solver.solve_prepared_with(dict, |solution| {
+        let arrangements = squares.find(&solution);
+
+        if arrangements.is_empty() {
+            return;
+        }
+
+        let mut found = found.lock().unwrap();
+
+        found.0 += 1;
+        for rows in arrangements {
+            found.1 += 1;
+
+            // (a square is written whole, or not at all)
+            //
+            let mut square = String::new();
+
+            for row in rows {
+                let word = ZWord::try_from(row.as_str()).expect("the rows are dictionary words");
+
+                square.push_str(&format!("\n{}", opts.speller().spell(&word)));
+            }
+            if opts.show_unused {
+                square.push_str(&format!("\n[{}]", opts.speller().unused(solution.unused())));
+            }
+            output_limit::println(square);
+        }
+    });
+
+    let (solutions, arrangements) = found.into_inner().unwrap();
+
+    if arrangements > 0 {
+        output_limit::println("");
+    }
+    output_limit::println(format_args!(
+        "{} word squares, from {} of {} solutions",
+        arrangements, solutions, stats.solutions
+    ));
+
+    if !output_limit::report() && stats.cancelled {
+        eprintln!("interrupted: these are only the squares found before Ctrl-C");
+    }
+    arrangements
+}
+
+// --retry-without-skip: look for sets that use every letter first, and
+// only when there are none, for sets that may leave letters unused,
+// saying which of the two the solutions are
+//
+fn run_retrying(dict: &PreparedDict, dict_hash: u64, opts: &Options) -> usize {
+    let strict = Options {
+        no_skip: true,
+        ..opts.clone()
+    };
+
+    // (only a quick look for one set, before the run proper)
+    //
+    let probe = match strict.builder().limit(1).build() {
+        Ok(probe) => probe,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            exit(2);
+        }
+    };
+
+    let opts = match probe.solve_prepared(dict).is_empty() {
+        true => {
+            eprintln!("no set of words uses every letter: these solutions leave some unused");
+            opts
+        }
+        false => {
+            eprintln!("these solutions use every letter");
+            &strict
+        }
+    };
+
+    let solver = match opts.builder().build() {
+        Ok(solver) => solver,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            exit(2);
+        }
+    };
+
+    run(&solver, dict, dict_hash, opts)
+}
+
+// with no full solution, settle for the largest sets of fewer words
+// that cover at least `coverage` letters. The words are disjoint and of
+// one length, so a set's coverage is just its size times the word
+// length, and the best near-misses are the solutions for the largest
+// set size that has any.
+//
+fn run_best_coverage(
+    dict: &PreparedDict,
+    dict_hash: u64,
+    coverage: usize,
+    opts: &Options,
+) -> usize {
+    let fewest = coverage.div_ceil(opts.word_len);
+
+    for size in (fewest..=opts.set_size).rev() {
+        let sized = Options {
+            set_size: size,
+            ..opts.clone()
+        };
+
+        let solver = match sized.builder().build() {
+            Ok(solver) => solver,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                exit(2);
+            }
+        };
+
+        // (only a quick look for one set, before the run proper)
+        //
+        let probe = match sized.builder().limit(1).build() {
+            Ok(probe) => probe,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                exit(2);
+            }
+        };
+
+        if probe.solve_prepared(dict).is_empty() {
+            if interrupt::interrupted() {
+                break;
+            }
+            continue;
+        }
+
+        if size < opts.set_size {
+            eprintln!(
+                "no full solution: the best sets cover {} letters, with {} words",
+                size * opts.word_len,
+                size
+            );
+        }
 
-    let timer = Instant::now();
+        return run(&solver, dict, dict_hash, &sized);
+    }
+
+    eprintln!("no sets of words cover at least {} letters", coverage);
+    0
+}
+
+fn run(solver: &Solver, dict: &PreparedDict, dict_hash: u64, opts: &Options) -> usize {
+    let summary = Summary::default();
+    let histogram = WordHistogram::default();
+    let by_length = LengthCounts::default();
+
+    let stats = if let Some(path) = &opts.sqlite {
+        write_sqlite(solver, dict, path, opts)
+    } else if opts.format == Format::Binary {
+        let writer = Mutex::new(binary_writer(opts));
+        let record_len = binary::record_len(opts.set_size);
+        let write = |solution: &Solution| {
+            if !output_limit::take(record_len) {
+                return;
+            }
+            let mut writer = writer.lock().unwrap();
+
+            if let Err(err) = writer
+                .write(solution)
+                .and_then(|()| match opts.stream_immediate {
+                    true => writer.flush(),
+                    false => Ok(()),
+                })
+            {
+                eprintln!("error: can't write output: {}", err);
+                exit(2);
+            }
+        };
+
+        let stats = if opts.is_streaming() {
+            solver.solve_prepared_with(dict, |solution| write(&solution))
+        } else {
+            let (solutions, stats) = solver.solve_prepared_stats(dict);
+
+            solutions.iter().for_each(write);
+            stats
+        };
+
+        if let Err(err) = writer.into_inner().unwrap().finish() {
+            eprintln!("error: can't write output: {}", err);
+            exit(2);
+        }
+        stats
+    } else if opts.is_streaming() {
+        solver.solve_prepared_with(dict, |solution| {
+            if opts.summary || opts.letters_report {
+                summary.add(&solution);
+            }
+            if opts.histogram {
+                histogram.add(&solution);
+            }
+            if opts.by_length {
+                by_length.add(&solution);
+            }
+            if !opts.summary_only && opts.is_new(&solution) {
+                match opts.stream_immediate {
+                    true => output_limit::println_flushed(display(&solution, opts)),
+                    false => output_limit::println(display(&solution, opts)),
+                }
+            }
+        })
+    } else {
+        let (mut solutions, stats) = solver.solve_prepared_stats(dict);
+
+        if opts.summary || opts.letters_report {
+            solutions.iter().for_each(|solution| summary.add(solution));
+        }
+        if opts.histogram {
+            solutions
+                .iter()
+                .for_each(|solution| histogram.add(solution));
+        }
+        if opts.by_length {
+            solutions
+                .iter()
+                .for_each(|solution| by_length.add(solution));
+        }
+        if !opts.summary_only {
+            solutions.retain(|solution| opts.is_new(solution));
+            print_solutions(solutions, dict_hash, opts);
+        }
+        stats
+    };
 
-    process(&words);
+    if stats.candidates == 0 && !opts.no_warn {
+        match opts.suggested_len(dict) {
+            Some(len) => eprintln!(
+                "warning: no words of {} letters found, but most of the dictionary's have {}: did you mean --len {}?",
+                describe_lengths(opts.lengths()),
+                len,
+                len
+            ),
+            None => eprintln!(
+                "warning: no words of length {} fit these constraints",
+                opts.word_len
+            ),
+        }
+    }
+
+    if !output_limit::report() && stats.cancelled {
+        eprintln!("interrupted: these are only the solutions found before Ctrl-C");
+    }
+    if stats.timed_out {
+        eprintln!("out of time: these are only the solutions found within the time budget");
+    }
 
-    println!("Elapsed ms [{}]", timer.elapsed().as_millis());
+    if opts.letters_report {
+        print_letters_report(&summary, stats.solutions);
+    }
+    if opts.histogram {
+        histogram.print(*opts.lengths().end());
+    }
+    if opts.by_length {
+        by_length.print();
+    }
+    if opts.summary {
+        summary.print(&opts.paths[0], dict, opts.lengths(), &stats);
+    }
+    stats.solutions
 }
 
-fn process(all_words: &String) {
-    let mut seen: HashSet<u32> = Default::default();
-    let mut words: Vec<_> = Vec::with_capacity(6000);
-    let mut freq: [(ZChar, u32); 26] = Default::default();
+// prints a --format binary stream as text, a solution per line with
+// its unused letter, as --show-unused would. Each mask is spelled as
+// the dictionary's first word with those letters, which is the one the
+// search kept, so the dictionary has to be the one the stream was made
+// from.
+//
+fn decode_binary(path: &str, opts: &Options) {
+    let fail = |err: String| -> ! {
+        eprintln!("error: can't decode '{}': {}", path, err);
+        exit(2);
+    };
+
+    let (word_len, records) = fs::read(path)
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| binary::read(&bytes))
+        .unwrap_or_else(|err| fail(err));
+
+    let dict = &opts.paths[0];
+    let text = opts.read_words(dict).unwrap_or_else(|err| fail(err));
+
+    let words: HashMap<u32, String> = fivewords::candidates(&text, word_len)
+        .map(|(word, mask)| (mask, word))
+        .collect();
 
-    for i in 0..26 {
-        freq[i].0 = ZChar(i as u8)
+    for record in records {
+        let spelled: Vec<&str> = record
+            .masks
+            .iter()
+            .map(|mask| match words.get(mask) {
+                Some(word) => word.as_str(),
+                None => fail(format!(
+                    "no word in '{}' has the letters '{}'",
+                    dict,
+                    fivewords::decode_mask(*mask).iter().collect::<String>()
+                )),
+            })
+            .collect();
+        let unused = match record.unused {
+            Some(letter) => letter.to_string(),
+            None => "none".to_string(),
+        };
+
+        output_limit::println(format_args!("{} [{}]", spelled.join(" "), unused));
     }
+    output_limit::report();
+}
 
-    'index_words: for word in all_words.lines() {
-        // we are looking for 5-letter words ONLY!
-        //
-        if word.len() != 5 {
-            continue;
+// run the solve into a database rather than onto stdout
+//
+#[cfg(feature = "sqlite")]
+fn write_sqlite(solver: &Solver, dict: &PreparedDict, path: &str, opts: &Options) -> Stats {
+    let fail = |err: String| -> ! {
+        eprintln!("error: can't write to '{}': {}", path, err);
+        exit(2);
+    };
+
+    let writer =
+        Mutex::new(sqlite::Writer::create(path, opts.set_size).unwrap_or_else(|err| fail(err)));
+    let write = |solution: &Solution| {
+        let words: Vec<String> = solution
+            .words()
+            .iter()
+            .map(|word| opts.speller().spell(word).to_string())
+            .collect();
+        let unused = match solution.unused() {
+            [] => String::new(),
+            letters => opts.speller().unused(letters).to_string(),
+        };
+
+        if let Err(err) = writer.lock().unwrap().write(&words, &unused) {
+            fail(err);
         }
+    };
 
-        let mut bits = 0;
-        let mut zwrd: ZWord = Default::default();
+    let stats = if opts.is_streaming() {
+        solver.solve_prepared_with(dict, |solution| write(&solution))
+    } else {
+        let (solutions, stats) = solver.solve_prepared_stats(dict);
 
-        for (i, c) in word.chars().enumerate() {
-            let z = ZChar::from(c);
-            let b = z.mask();
+        solutions.iter().for_each(write);
+        stats
+    };
 
-            // if we get a duplicate letter (e.g. floor - has two o's)
-            // this isn't a valid 5-letter word as all letters MUST
-            // appear only ONCE
-            //
-            if bits & b != 0 {
-                continue 'index_words;
-            }
+    let rows = writer
+        .into_inner()
+        .unwrap()
+        .finish()
+        .unwrap_or_else(|err| fail(err));
 
-            // add this letter to the word bitfield, and increase
-            // the letter frequency count
-            //
-            bits |= b;
-            zwrd[i] = z;
-            freq[z.ord()].1 += 1;
+    println!("{} solutions written to {}", rows, path);
+    stats
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn write_sqlite(_: &Solver, _: &PreparedDict, _: &str, _: &Options) -> Stats {
+    unreachable!("--sqlite is only accepted with the 'sqlite' feature")
+}
+
+fn binary_writer(opts: &Options) -> binary::Writer<io::Stdout> {
+    match binary::Writer::new(io::stdout(), opts.word_len, opts.set_size) {
+        Ok(writer) => writer,
+        Err(err) => {
+            eprintln!("error: can't write output: {}", err);
+            exit(2);
         }
+    }
+}
 
-        // we don't need anagrams of words, so just take the first
-        // anagram (the unique alphabet bit-pattern).
-        //
-        if seen.insert(bits) {
-            words.push(zwrd);
+// print a solve's solutions, once they're all in
+//
+fn print_solutions(mut solutions: Vec<Solution>, dict_hash: u64, opts: &Options) {
+    match opts.format {
+        Format::Text if opts.per_letter_limit.is_some() => {
+            solutions.sort_by_key(|s| s.unused().first().map(ZChar::ord));
+
+            for group in solutions.chunk_by(|a, b| a.unused().first() == b.unused().first()) {
+                match group[0].unused().first() {
+                    Some(&letter) => output_limit::println(format_args!(
+                        "[{}] ({} shown)",
+                        opts.speller().letter(letter),
+                        group.len()
+                    )),
+                    None => output_limit::println(format_args!("[none] ({} shown)", group.len())),
+                }
+
+                for solution in group {
+                    output_limit::println(format_args!("    {}", display(solution, opts)));
+                }
+            }
+        }
+        Format::Text if opts.group_by_first => {
+            // the first word is the one filling the rarest letter its
+            // solutions cover, so each group fans out from one anchor
+            //
+            solutions.sort_by_cached_key(|s| s.words()[0].to_string());
+
+            for group in solutions.chunk_by(|a, b| a.words()[0].iter().eq(b.words()[0].iter())) {
+                let plural = if group.len() == 1 { "" } else { "s" };
+
+                output_limit::println(format_args!(
+                    "{} ({} solution{})",
+                    opts.speller().spell(&group[0].words()[0]),
+                    group.len(),
+                    plural
+                ));
+
+                for solution in group {
+                    output_limit::println(format_args!("    {}", display(solution, opts)));
+                }
+            }
+        }
+        Format::Text => {
+            for solution in &solutions {
+                output_limit::println(display(solution, opts));
+            }
+        }
+        Format::Json => print_json(&solutions, dict_hash, opts),
+        Format::Ids => {
+            for solution in &solutions {
+                output_limit::println(display(solution, opts));
+            }
         }
+        Format::Binary => unreachable!("binary output is written as it's found"),
     }
+}
 
-    freq.sort_unstable_by_key(|x| x.1);
+// answers every query in a batch file, over the one prepared
+// dictionary. Each line (bar blank lines and '#'
+// comments) holds a query's options as they'd be given on the command
+// line, on top of those actually given there, and its results follow
+// a "# <query>" header. With --batch-dedup, a solution an earlier query
+// already printed isn't printed again, and a note after the results
+// counts those left out by the line of the query that printed them.
+//
+fn run_batch(dict: &PreparedDict, dict_hash: u64, path: &str, args: &[String]) -> usize {
+    let batch = match fs::read_to_string(path) {
+        Ok(batch) => batch,
+        Err(err) => {
+            eprintln!("error: can't read batch file '{}': {}", path, err);
+            exit(2);
+        }
+    };
 
-    // build bitmask LUT from frequencies. The idea is that each
-    // character gets assigned a new bit position, based upon its
-    // frequency in the valid words.
+    // check every query before we start, rather than failing halfway
     //
-    // eg:
-    //   ('a' x 100) : mask_lut[0] = (0b...0000_0000_0000_0001, 0)
-    //   ('e' x 89)  : mask_lut[4] = (0b...0000_0000_0000_0010, 1)
-    //   ('i' x 77)  : mask_lut[8] = (0b...0000_0000_0000_0100, 2)
+    let mut queries = Vec::new();
+
+    for (n, line) in batch.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_query(line, args) {
+            Ok((opts, solver)) => queries.push((n + 1, line, opts, solver)),
+            Err(msg) => {
+                eprintln!("error: {} line {}: {}", path, n + 1, msg);
+                exit(2);
+            }
+        }
+    }
+
+    // the dictionary is normalized once for the whole batch, so that is
+    // the one option queries can't change
     //
-    let mut mask_lut: [(u32, usize); 26] = Default::default();
+    let spellings = match queries
+        .iter()
+        .any(|(_, _, opts, _)| opts.casing == Casing::AsInput)
+    {
+        true => Arc::new(spellings(dict)),
+        false => Arc::default(),
+    };
+
+    let shown = Arc::new(Shown::default());
+    let mut found = 0;
+
+    for (n, line, opts, solver) in &mut queries {
+        if interrupt::interrupted() || output_limit::truncated() {
+            break;
+        }
+
+        if opts.casing == Casing::AsInput {
+            opts.spellings = spellings.clone();
+        }
+        if opts.batch_dedup {
+            opts.shown = Some(shown.clone());
+            opts.query = *n;
+        }
 
-    for (i, &(z, _)) in freq.iter().enumerate() {
-        mask_lut[z.ord()] = (1u32 << i, i);
+        output_limit::println(format_args!("# {}", line));
+        found += match opts.retry_without_skip {
+            true => run_retrying(dict, dict_hash, opts),
+            false => run(solver, dict, dict_hash, opts),
+        };
+
+        if let Some(note) = shown.take_repeats() {
+            output_limit::println(format_args!("# {}", note));
+        }
     }
+    found
+}
+
+// --batch-dedup: each solution a batch has printed, by id, with the
+// line of the query that printed it first, and how many of the current
+// query's solutions each earlier query already printed
+//
+#[derive(Default)]
+struct Shown {
+    first: Mutex<HashMap<u64, usize>>,
+    repeats: Mutex<BTreeMap<usize, usize>>,
+}
 
-    // give each word a new mask, where the most-frequent letters
-    // appear closer to the LSB (least significant bit) in the
-    // bitfield.
+impl Shown {
+    // whether `solution` is new to the batch, for the query on line
+    // `query` to print
     //
-    // eg: "cats"
-    // ('c' x 20)  = 0b...0000_0100_0000_0000
-    // ('a' x 100) = 0b...0000_0000_0000_0001  < most freq' so more LSB
-    // ('t' x 33)  = 0b...0000_0000_0100_0000
-    // ('s' x 40)  = 0b...0000_0000_0000_1000
+    fn first(&self, solution: &Solution, query: usize) -> bool {
+        let first = *self
+            .first
+            .lock()
+            .unwrap()
+            .entry(solution.id())
+            .or_insert(query);
+
+        if first != query {
+            *self.repeats.lock().unwrap().entry(first).or_default() += 1;
+        }
+        first == query
+    }
+
+    // a note of the current query's solutions that were left out, if
+    // any were, starting afresh for the next query
     //
-    // We also stick all words with the same LSB into a bucket, so
-    // we can easily look them up. This means we can EFFICIENTLY
-    // fil a target bit-pattern quickly.
+    fn take_repeats(&self) -> Option<String> {
+        let repeats = std::mem::take(&mut *self.repeats.lock().unwrap());
+
+        if repeats.is_empty() {
+            return None;
+        }
+
+        let total: usize = repeats.values().sum();
+        let by_line: Vec<String> = repeats
+            .iter()
+            .map(|(line, count)| format!("{} by line {}", count, line))
+            .collect();
+
+        Some(format!("{} already shown: {}", total, by_line.join(", ")))
+    }
+}
+
+// --warm-pool: the one pool every solve of the run searches on. The
+// first solver to ask starts it (with as many threads as its --threads,
+// or rayon's default), and the rest share it, so a batch's queries
+// don't each wait on threads of their own.
+//
+fn warm_pool(threads: Option<usize>) -> Arc<rayon::ThreadPool> {
+    static POOL: OnceLock<Arc<rayon::ThreadPool>> = OnceLock::new();
+
+    POOL.get_or_init(|| {
+        let mut pool = rayon::ThreadPoolBuilder::new();
+
+        if let Some(threads) = threads {
+            pool = pool.num_threads(threads);
+        }
+
+        match pool.build() {
+            Ok(pool) => Arc::new(pool),
+            Err(err) => {
+                eprintln!("error: can't start the thread pool: {}", err);
+                exit(2);
+            }
+        }
+    })
+    .clone()
+}
+
+fn parse_query(line: &str, args: &[String]) -> Result<(Options, Solver), String> {
+    let words = split_query(line)?;
+
+    // the query on its own, to catch options that only make sense for
+    // the run as a whole
     //
-    let mut lbit_lut: [Vec<u32>; 26] = Default::default();
-    let mut word_lut: HashMap<u32, ZWord> = Default::default();
+    let own = Options::from_args(words.iter().cloned())?;
+
+    if !own.paths.is_empty() {
+        return Err("a query can't name a dictionary".to_string());
+    }
+    if own.batch.is_some() || own.resume.is_some() || own.benchmark_dictionaries {
+        return Err(
+            "--batch, --resume and --benchmark-dictionaries can't be used in a query".to_string(),
+        );
+    }
+    if own.preset.is_some() {
+        return Err("--use applies to the whole batch, not a single query".to_string());
+    }
+    if own.format != Format::Text {
+        return Err("queries only support text output".to_string());
+    }
+    #[cfg(feature = "unicode")]
+    if own.normalize != Normalize::None {
+        return Err("--normalize applies to the whole batch, not a single query".to_string());
+    }
 
-    for word in words {
-        let mut new_bits = 0;
-        let mut lowbit = 26;
+    let opts = Options::from_args(args.iter().cloned().chain(words))?;
+    let solver = opts.builder().build().map_err(|err| err.to_string())?;
 
-        for z in *word {
-            let idx = z.ord();
-            let msk = mask_lut[idx].0;
-            let lsb = mask_lut[idx].1;
+    Ok((opts, solver))
+}
 
-            new_bits |= msk;
+// split a query into words at whitespace, keeping anything inside
+// double quotes together (for templates with spaces in them)
+//
+fn split_query(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = None;
+    let mut quoted = false;
 
-            lowbit = lowbit.min(lsb);
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                word.get_or_insert_with(String::new);
+            }
+            _ if c.is_whitespace() && !quoted => words.extend(word.take()),
+            _ => word.get_or_insert_with(String::new).push(c),
         }
+    }
 
-        lbit_lut[lowbit].push(new_bits);
-        word_lut.insert(new_bits, word);
+    if quoted {
+        return Err("unclosed '\"'".to_string());
     }
 
-    // do the search, trying to fill our first free bit in our
-    // final 'mask', using the LSB lookups.
-    //
-    fn search(
-        selected: &mut [u32; 5],
-        lut: &[Vec<u32>; 26],
-        mask: u32,
-        depth: usize,
-        word_lut: &HashMap<u32, ZWord>,
-    ) {
-        if depth == 5 {
-            println!(
-                "{} {} {} {} {}",
-                word_lut[&selected[0]],
-                word_lut[&selected[1]],
-                word_lut[&selected[2]],
-                word_lut[&selected[3]],
-                word_lut[&selected[4]]
-            );
-            return;
+    words.extend(word);
+    Ok(words)
+}
+
+// a streamed run that records each finished seed (and its output) in a
+// checkpoint file, replaying whatever an earlier run already finished
+// and only searching the seeds it didn't get to.
+//
+fn run_resumable(
+    solver: &Solver,
+    dict: &PreparedDict,
+    dict_hash: u64,
+    path: &str,
+    opts: &Options,
+) -> usize {
+    let header = format!("dict={:016x} {}", dict_hash, opts.fingerprint());
+
+    let mut checkpoint = match Checkpoint::open(path, header) {
+        Ok(checkpoint) => checkpoint,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            exit(2);
+        }
+    };
+
+    if let Some(interval) = opts.checkpoint_interval {
+        checkpoint.save_every(interval);
+    }
+
+    let replayed = checkpoint.lines().count();
+
+    for line in checkpoint.lines() {
+        output_limit::println(line);
+    }
+
+    let checkpoint = Mutex::new(checkpoint);
+
+    let stats = solver.solve_prepared_seeds(
+        dict,
+        |seed| !checkpoint.lock().unwrap().is_done(seed),
+        |seed, solution| {
+            let line = display(&solution, opts).to_string();
+
+            // (a solution past the limit is still kept in the
+            // checkpoint, to be printed by the run that resumes it)
+            //
+            output_limit::println(&line);
+            checkpoint.lock().unwrap().record(seed, line);
+        },
+        |seed| {
+            if let Err(err) = checkpoint.lock().unwrap().finish(seed) {
+                eprintln!("warning: can't write checkpoint '{}': {}", path, err);
+            }
+        },
+    );
+
+    if let Err(err) = checkpoint.into_inner().unwrap().flush() {
+        eprintln!("warning: can't write checkpoint '{}': {}", path, err);
+    }
+    output_limit::report();
+    replayed + stats.solutions
+}
+
+fn display<'a>(solution: &'a Solution, opts: &'a Options) -> SolutionDisplay<'a> {
+    SolutionDisplay {
+        solution,
+        speller: opts.speller(),
+        template: opts.template.as_ref(),
+        show_unused: opts.show_unused,
+        unused_only: opts.unused_only,
+        show_masks: opts.show_masks,
+        grid: opts.grid,
+        ids: (opts.format == Format::Ids).then_some(&*opts.ids),
+    }
+}
+
+struct SolutionDisplay<'a> {
+    solution: &'a Solution,
+    speller: Speller<'a>,
+    template: Option<&'a Template>,
+    show_unused: bool,
+    unused_only: bool,
+    show_masks: bool,
+    grid: bool,
+    ids: Option<&'a HashMap<String, usize>>,
+}
+
+impl Display for SolutionDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // each word as its number in the id table
+        //
+        if let Some(ids) = self.ids {
+            for (i, word) in self.solution.words().iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", ids[&word.to_string()])?;
+            }
+
+            if self.show_unused {
+                write!(f, " [{}]", self.speller.unused(self.solution.unused()))?;
+            }
+            return Ok(());
         }
 
-        // find the lowest free bit (next low-frequency character)
+        // a template lays out the whole line itself
         //
-        let lowbit = mask.trailing_ones();
-        let words = &lut[lowbit as usize];
+        if let Some(template) = self.template {
+            let words: Vec<_> = self
+                .solution
+                .words()
+                .iter()
+                .map(|word| self.speller.spell(word))
+                .collect();
+            let unused = self.speller.unused(self.solution.unused());
 
-        if cfg!(debug_assertions) {
-            println!(
-                "free lowbit [{:#02}] with mask [{:#028b}] at depth {} :: searching {} words...",
-                lowbit,
-                mask,
-                depth,
-                words.len()
-            );
+            return write!(f, "{}", template.render(&words, unused));
+        }
+
+        // just the letters, for profiling which ones solutions omit
+        //
+        if self.unused_only {
+            return write!(f, "{}", self.speller.unused(self.solution.unused()));
         }
 
-        for &bits in words {
-            if mask & bits == 0 {
-                selected[depth] = bits;
-                search(selected, lut, mask | bits, depth + 1, word_lut);
+        // one word per row, with a blank line after each square
+        //
+        if self.grid {
+            for word in self.solution.words() {
+                let spelled = self.speller.spell(word).to_string();
+
+                for (i, c) in spelled.chars().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                writeln!(f)?;
+            }
+
+            if self.show_unused {
+                writeln!(f, "[{}]", self.speller.unused(self.solution.unused()))?;
             }
+            return Ok(());
         }
-    }
 
-    (0..27).into_par_iter().for_each(|i| {
-        let mask = 1 << i;
-        let mut selected: [u32; 5] = Default::default();
+        if self.show_masks {
+            let words = self.solution.words().iter();
 
-        search(&mut selected, &lbit_lut, mask, 0, &word_lut);
-    });
+            for (i, (word, &mask)) in words.zip(self.solution.masks()).enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}[{}]", self.speller.spell(word), MaskDisplay(mask))?;
+            }
+        } else {
+            for (i, word) in self.solution.words().iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", self.speller.spell(word))?;
+            }
+        }
+
+        if self.show_unused {
+            write!(f, " [{}]", self.speller.unused(self.solution.unused()))?;
+        }
+        Ok(())
+    }
 }
 
-const U8A: u8 = 'a' as u8;
+// the casing --normalize-output asks for, and the spellings to take
+// words from for as-input
+//
+#[derive(Clone, Copy)]
+struct Speller<'a> {
+    casing: Casing,
+    spellings: &'a HashMap<String, String>,
+    tokens: Option<&'a Tokens>,
+}
+
+impl<'a> Speller<'a> {
+    fn spell(self, word: &'a ZWord) -> Spelled<'a> {
+        Spelled {
+            word,
+            speller: self,
+        }
+    }
 
-#[derive(Default, Copy, Clone)]
-struct ZChar(u8);
+    fn unused(self, letters: &'a [ZChar]) -> UnusedDisplay<'a> {
+        UnusedDisplay(letters, self.tokens)
+    }
 
-impl ZChar {
-    fn from(c: char) -> Self {
-        ZChar((c.to_ascii_lowercase() as u8) - U8A)
+    // a single letter, or the token it stands in for
+    //
+    fn letter(self, z: ZChar) -> String {
+        match self.tokens {
+            Some(tokens) => tokens.token(z).to_string(),
+            None => z.to_string(),
+        }
     }
+}
+
+struct Spelled<'a> {
+    word: &'a ZWord,
+    speller: Speller<'a>,
+}
+
+impl Display for Spelled<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(tokens) = self.speller.tokens {
+            return write!(f, "{}", tokens.spell(self.word));
+        }
 
-    fn chr(&self) -> char {
-        (&self.0 + U8A) as char
+        match self.speller.casing {
+            Casing::Lower => write!(f, "{}", self.word),
+            Casing::Upper => {
+                for z in self.word {
+                    write!(f, "{}", z.chr().to_ascii_uppercase())?;
+                }
+                Ok(())
+            }
+            Casing::AsInput => match self.speller.spellings.get(&self.word.to_string()) {
+                Some(spelling) => write!(f, "{}", spelling),
+                None => write!(f, "{}", self.word),
+            },
+        }
     }
+}
 
-    fn mask(&self) -> u32 {
-        1 << self.0
+// each word's first spelling in the dictionary, keyed by its letters
+//
+fn spellings(dict: &PreparedDict) -> HashMap<String, String> {
+    let mut spellings = HashMap::new();
+
+    for (word, spelling) in dict.spellings() {
+        spellings
+            .entry(word.to_string())
+            .or_insert_with(|| spelling.to_string());
     }
+    spellings
+}
+
+// the unused letters of a solution as a single word (or the unused
+// tokens, spaced apart), or "none" when the solution covers the whole
+// alphabet
+//
+struct UnusedDisplay<'a>(&'a [ZChar], Option<&'a Tokens>);
+
+impl Display for UnusedDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "none");
+        }
+        if let Some(tokens) = self.1 {
+            return write!(f, "{}", tokens.list(self.0));
+        }
 
-    fn ord(&self) -> usize {
-        self.0 as usize
+        for z in self.0 {
+            write!(f, "{}", z)?;
+        }
+        Ok(())
     }
 }
 
-impl Display for ZChar {
+// a remapped word mask as 26 binary digits (or as many as reach its top
+// bit, over a larger alphabet), grouped in fours from the least
+// significant bit, e.g. 0b00_0000_0000_0001_0110_0000_1000
+//
+struct MaskDisplay(u64);
+
+impl Display for MaskDisplay {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.chr())
+        let digits = 26.max(64 - self.0.leading_zeros());
+
+        write!(f, "0b")?;
+
+        for bit in (0..digits).rev() {
+            write!(f, "{}", (self.0 >> bit) & 1)?;
+
+            if bit > 0 && bit % 4 == 0 {
+                write!(f, "_")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// the JSON output is wrapped in a versioned envelope, so consumers can
+// tell which shape of output (and which parameters and dictionary)
+// they are looking at. Bump JSON_VERSION whenever the shape changes.
+//
+const JSON_VERSION: u32 = 2;
+
+// (the envelope's end, as long as it can be, which the output limit
+// keeps room for so the JSON is always whole)
+//
+const JSON_END: &str = "\n  ]\n}\n";
+
+fn print_json(solutions: &[Solution], dict_hash: u64, opts: &Options) {
+    use std::fmt::Write as _;
+
+    let mut head = String::new();
+
+    let _ = writeln!(head, "{{");
+    let _ = writeln!(head, "  \"version\": {},", JSON_VERSION);
+    // (every option that changes which solutions are found, or which
+    // of them a limit keeps, so the run can be repeated from these)
+    //
+    let lengths = opts.lengths();
+    let tokens = opts.tokens.as_ref().map_or(&[][..], Tokens::all);
+
+    let _ = writeln!(head, "  \"params\": {{");
+    let _ = writeln!(head, "    \"len\": {},", lengths.start());
+    let _ = writeln!(head, "    \"max_len\": {},", lengths.end());
+    let _ = writeln!(head, "    \"count\": {},", opts.set_size);
+    let _ = writeln!(head, "    \"skip\": {},", !opts.no_skip);
+    let _ = writeln!(head, "    \"allowed\": {},", json_opt_str(&opts.allowed));
+    let _ = writeln!(head, "    \"required\": {},", json_opt_str(&opts.required));
+    let _ = writeln!(head, "    \"excluded\": {},", json_opt_str(&opts.excluded));
+    let _ = writeln!(head, "    \"disjoint\": {},", json_opt_str(&opts.disjoint));
+    let _ = writeln!(
+        head,
+        "    \"must_include\": {},",
+        json_opt_str(&opts.must_include.as_ref().map(|word| word.to_lowercase()))
+    );
+    let _ = writeln!(head, "    \"contains\": {},", json_opt_str(&opts.contains));
+    let _ = writeln!(
+        head,
+        "    \"intersect\": {},",
+        json_opt_str(&opts.intersect)
+    );
+    let _ = writeln!(head, "    \"forbidden\": {},", json_list(&opts.forbidden));
+    let _ = writeln!(head, "    \"tokens\": {},", json_list(tokens));
+    let _ = writeln!(head, "    \"top_words\": {},", json_opt(opts.top_words));
+    let _ = writeln!(
+        head,
+        "    \"freq_source\": {},",
+        json_opt_str(&opts.freq_source)
+    );
+    let _ = writeln!(head, "    \"min_unique\": {},", json_opt(opts.min_unique));
+    let _ = writeln!(head, "    \"max_unique\": {},", json_opt(opts.max_unique));
+    let _ = writeln!(
+        head,
+        "    \"min_coverage\": {},",
+        json_opt(opts.min_coverage)
+    );
+    let _ = writeln!(head, "    \"require_vowel\": {},", opts.require_vowel);
+    let _ = writeln!(head, "    \"y_vowel\": {},", opts.y_vowel);
+    let _ = writeln!(
+        head,
+        "    \"require_anagrammable\": {},",
+        opts.require_anagrammable
+    );
+    let _ = writeln!(
+        head,
+        "    \"dedup\": \"{}\",",
+        match opts.dedup {
+            Dedup::Bitmask => "bitmask",
+            Dedup::Original => "original",
+        }
+    );
+    let _ = writeln!(
+        head,
+        "    \"anagram\": \"{}\",",
+        match opts.anagram {
+            Anagram::First => "first",
+            Anagram::Alpha => "alpha",
+        }
+    );
+    let _ = writeln!(
+        head,
+        "    \"max_spellings\": {},",
+        json_opt(opts.max_spellings)
+    );
+    let _ = writeln!(head, "    \"limit\": {},", json_opt(opts.limit));
+    let _ = writeln!(
+        head,
+        "    \"per_letter_limit\": {},",
+        json_opt(opts.per_letter_limit)
+    );
+    let _ = writeln!(
+        head,
+        "    \"seed_order\": \"{}\",",
+        match opts.seed_order {
+            SeedOrder::Natural => "natural",
+            SeedOrder::SizeDesc => "size-desc",
+            SeedOrder::SizeAsc => "size-asc",
+        }
+    );
+    let _ = writeln!(
+        head,
+        "    \"order\": \"{}\",",
+        match opts.order {
+            Order::Found => "found",
+            Order::Alphabetical => "alpha",
+            Order::Unused => "unused",
+        }
+    );
+    let _ = writeln!(
+        head,
+        "    \"time_budget_ms\": {},",
+        json_opt(opts.time_budget.map(|budget| budget.as_millis() as usize))
+    );
+    let _ = writeln!(head, "    \"dictionary_hash\": \"{:016x}\"", dict_hash);
+    let _ = writeln!(head, "  }},");
+    let _ = writeln!(head, "  \"solutions\": [");
+
+    // (a limit too small for even the envelope gets nothing)
+    //
+    if !output_limit::take(head.len() + JSON_END.len()) {
+        return;
+    }
+    print!("{}", head);
+
+    // (each solution is followed by a comma only once there's another
+    // to write, as the output limit may cut the list short)
+    //
+    let mut written = 0;
+
+    for solution in solutions {
+        let words: Vec<_> = solution
+            .words()
+            .iter()
+            .map(|w| JsonStr(opts.speller().spell(w)).to_string())
+            .collect();
+        let unused: Vec<_> = solution
+            .unused()
+            .iter()
+            .map(|&z| JsonStr(opts.speller().letter(z)).to_string())
+            .collect();
+
+        // the masks are opt-in, like in the text output
+        //
+        let masks = if opts.show_masks {
+            let masks: Vec<_> = solution
+                .masks()
+                .iter()
+                .map(|&mask| format!("\"{}\"", MaskDisplay(mask)))
+                .collect();
+
+            format!(", \"masks\": [{}]", masks.join(", "))
+        } else {
+            String::new()
+        };
+
+        let item = format!(
+            "    {{\"id\": \"{:016x}\", \"words\": [{}], \"unused\": [{}]{}}}",
+            solution.id(),
+            words.join(", "),
+            unused.join(", "),
+            masks,
+        );
+
+        if !output_limit::take(item.len() + 2) {
+            break;
+        }
+        print!("{}{}", if written > 0 { ",\n" } else { "" }, item);
+        written += 1;
+    }
+
+    match written {
+        0 => print!("{}", &JSON_END[1..]),
+        _ => print!("{}", JSON_END),
     }
 }
 
-#[derive(Default, Copy, Clone)]
-struct ZWord([ZChar; 5]);
+// an --event-log line: one JSON object per step of the search, e.g.
+//
+//   {"event": "enter", "depth": 1, "letter": "x", "words": 8}
+//   {"event": "try", "depth": 1, "word": "foxes"}
+//
+struct EventJson<'a>(&'a Event, Option<&'a Tokens>);
+
+impl Display for EventJson<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let speller = Speller {
+            casing: Casing::Lower,
+            spellings: &HashMap::new(),
+            tokens: self.1,
+        };
+        let word = |word: &ZWord| speller.spell(word).to_string();
+        let letter = |letter: &ZChar| speller.letter(*letter);
+        let words = |words: &[ZWord]| {
+            let words: Vec<_> = words.iter().map(|w| JsonStr(word(w)).to_string()).collect();
+
+            words.join(", ")
+        };
 
-impl Deref for ZWord {
-    type Target = [ZChar; 5];
+        match self.0 {
+            Event::Seed { seed: Some(seed) } => {
+                write!(
+                    f,
+                    "{{\"event\": \"seed\", \"seed\": {}}}",
+                    JsonStr(letter(seed))
+                )
+            }
+            Event::Seed { seed: None } => write!(f, "{{\"event\": \"seed\", \"seed\": null}}"),
+            Event::Enter {
+                depth,
+                letter: held,
+                words,
+            } => write!(
+                f,
+                "{{\"event\": \"enter\", \"depth\": {}, \"letter\": {}, \"words\": {}}}",
+                depth,
+                JsonStr(letter(held)),
+                words
+            ),
+            Event::Try { depth, word: tried } => write!(
+                f,
+                "{{\"event\": \"try\", \"depth\": {}, \"word\": {}}}",
+                depth,
+                JsonStr(word(tried))
+            ),
+            Event::Skip {
+                depth,
+                letter: skipped,
+            } => write!(
+                f,
+                "{{\"event\": \"skip\", \"depth\": {}, \"letter\": {}}}",
+                depth,
+                JsonStr(letter(skipped))
+            ),
+            Event::Prune { depth } => {
+                write!(f, "{{\"event\": \"prune\", \"depth\": {}}}", depth)
+            }
+            Event::Solution { words: placed } => write!(
+                f,
+                "{{\"event\": \"solution\", \"words\": [{}]}}",
+                words(placed)
+            ),
+        }
+    }
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+fn json_opt(value: Option<usize>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "null".to_string(),
     }
 }
 
-impl DerefMut for ZWord {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+fn json_list(values: &[impl AsRef<str>]) -> String {
+    let values: Vec<_> = values
+        .iter()
+        .map(|value| JsonStr(value.as_ref()).to_string())
+        .collect();
+
+    format!("[{}]", values.join(", "))
+}
+
+fn json_opt_str(value: &Option<String>) -> String {
+    match value {
+        Some(value) => JsonStr(value).to_string(),
+        None => "null".to_string(),
     }
 }
 
-impl Display for ZWord {
+// a JSON string: quoted, and escaped as it's written. Everything that
+// goes into the JSON output as a string goes through this, as words
+// and letters may be --tokens, which can be any text bar whitespace.
+//
+struct JsonStr<T>(T);
+
+impl<T: Display> Display for JsonStr<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}{}{}{}{}",
-            self.0[0].chr(),
-            self.0[1].chr(),
-            self.0[2].chr(),
-            self.0[3].chr(),
-            self.0[4].chr(),
-        )
+        use std::fmt::Write as _;
+
+        struct Escaped<'a, 'b>(&'a mut Formatter<'b>);
+
+        impl std::fmt::Write for Escaped<'_, '_> {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                for c in s.chars() {
+                    match c {
+                        '"' => self.0.write_str("\\\"")?,
+                        '\\' => self.0.write_str("\\\\")?,
+                        '\n' => self.0.write_str("\\n")?,
+                        '\r' => self.0.write_str("\\r")?,
+                        '\t' => self.0.write_str("\\t")?,
+                        c if c < ' ' => write!(self.0, "\\u{:04x}", c as u32)?,
+                        c => self.0.write_char(c)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        f.write_char('"')?;
+        write!(Escaped(f), "{}", self.0)?;
+        f.write_char('"')
+    }
+}
+
+// FNV-1a, used to fingerprint the dictionary. We want something that
+// is stable across runs, platforms and compiler versions, which rules
+// out the std hashers.
+//
+const FNV1A_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// hashes everything read through it, so a dictionary can be hashed in
+// the same pass that prepares it
+//
+struct Fnv1aReader<R> {
+    inner: R,
+    hash: u64,
+}
+
+impl<R> Fnv1aReader<R> {
+    fn new(inner: R) -> Self {
+        Fnv1aReader {
+            inner,
+            hash: FNV1A_BASIS,
+        }
+    }
+}
+
+impl<R: io::Read> io::Read for Fnv1aReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+
+        self.hash = fnv1a(self.hash, &buf[..n]);
+        Ok(n)
     }
 }
@@ -0,0 +1,69 @@
+use std::fs;
+use std::path::Path;
+
+use crate::split_query;
+
+// the manifest --use reads when --manifest doesn't name another
+//
+pub const DEFAULT_MANIFEST: &str = "fivewords.manifest";
+
+// a named dictionary from a manifest, with the options it's usually
+// solved with. The manifest is plain text, one entry per line (bar
+// blank lines and '#' comments):
+//
+//   # name          dictionary        options
+//   wordle-answers  answers.txt       --len 5 --count 5
+//   short           lists/short.txt   --len 4 --count 6 --limit 100
+//
+// The options are written as on the command line (quotes included),
+// and a relative dictionary path is taken from the manifest's own
+// directory, so the manifest can move along with its dictionaries.
+//
+pub struct Preset {
+    pub path: String,
+    pub args: Vec<String>,
+}
+
+pub fn find(manifest: &str, name: &str) -> Result<Preset, String> {
+    let text = fs::read_to_string(manifest)
+        .map_err(|err| format!("can't read manifest '{}': {}", manifest, err))?;
+
+    let dir = Path::new(manifest).parent().unwrap_or(Path::new(""));
+    let mut found = None;
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let bad = |msg: &str| format!("{} line {}: {}", manifest, n + 1, msg);
+
+        let mut words = split_query(line).map_err(|msg| bad(&msg))?.into_iter();
+
+        let (entry, path) = match (words.next(), words.next()) {
+            (Some(entry), Some(path)) => (entry, path),
+            _ => return Err(bad("expected a name and a dictionary")),
+        };
+        let args: Vec<String> = words.collect();
+
+        if args.iter().any(|arg| arg == "--use" || arg == "--manifest") {
+            return Err(bad("an entry can't --use another"));
+        }
+
+        if entry != name {
+            continue;
+        }
+        if found.is_some() {
+            return Err(bad(&format!("'{}' is already defined", name)));
+        }
+
+        found = Some(Preset {
+            path: dir.join(path).to_string_lossy().into_owned(),
+            args,
+        });
+    }
+
+    found.ok_or_else(|| format!("no dictionary named '{}' in manifest '{}'", name, manifest))
+}
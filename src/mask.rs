@@ -0,0 +1,93 @@
+use core::hash::Hash;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+// the integer the search keeps (remapped) letter masks in. The index
+// and search are written against this trait, monomorphized per width,
+// so the usual alphabets (the 26 letters, or as many as 32 symbols)
+// are searched on a u32, and only larger ones on a u64, with no
+// branching on the width as the search runs.
+//
+pub(crate) trait Mask:
+    Copy
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Send
+    + Sync
+    + BitAnd<Output = Self>
+    + BitAndAssign
+    + BitOr<Output = Self>
+    + BitOrAssign
+    + Not<Output = Self>
+{
+    const ZERO: Self;
+
+    // just bit `i`
+    //
+    fn bit(i: u32) -> Self;
+
+    // the lowest `n` bits (all of them, when `n` is the width or more)
+    //
+    fn low(n: u32) -> Self;
+
+    fn trailing_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+    fn count_ones(self) -> u32;
+
+    // clear the lowest set bit
+    //
+    fn clear_lowest(self) -> Self;
+
+    fn widen(self) -> u64;
+}
+
+macro_rules! impl_mask {
+    ($($t:ty),*) => {
+        $(
+            impl Mask for $t {
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn bit(i: u32) -> Self {
+                    1 << i
+                }
+
+                #[inline]
+                fn low(n: u32) -> Self {
+                    match n < <$t>::BITS {
+                        true => (1 << n) - 1,
+                        false => !0,
+                    }
+                }
+
+                #[inline]
+                fn trailing_ones(self) -> u32 {
+                    <$t>::trailing_ones(self)
+                }
+
+                #[inline]
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+
+                #[inline]
+                fn count_ones(self) -> u32 {
+                    <$t>::count_ones(self)
+                }
+
+                #[inline]
+                fn clear_lowest(self) -> Self {
+                    self & self.wrapping_sub(1)
+                }
+
+                #[inline]
+                fn widen(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_mask!(u32, u64);
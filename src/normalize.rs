@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// How dictionary words are normalized before their letters are read.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Normalize {
+    /// Use words exactly as they appear.
+    #[default]
+    None,
+    /// Compose to Unicode NFC, so "e" followed by a combining accent
+    /// and a precomposed "é" are the same letter.
+    Nfc,
+    /// Decompose, then drop the combining marks, leaving base letters
+    /// ("café" becomes "cafe").
+    Strip,
+}
+
+impl Normalize {
+    pub(crate) fn apply<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        match self {
+            Normalize::None => Cow::Borrowed(word),
+            Normalize::Nfc if is_nfc(word) => Cow::Borrowed(word),
+            Normalize::Nfc => Cow::Owned(word.nfc().collect()),
+            Normalize::Strip if word.is_ascii() => Cow::Borrowed(word),
+            Normalize::Strip => Cow::Owned(word.nfd().filter(|&c| !is_combining_mark(c)).collect()),
+        }
+    }
+}
+
+impl FromStr for Normalize {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Normalize::None),
+            "nfc" => Ok(Normalize::Nfc),
+            "strip" => Ok(Normalize::Strip),
+            _ => Err(()),
+        }
+    }
+}
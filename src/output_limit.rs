@@ -0,0 +1,94 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::process::exit;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use crate::interrupt;
+
+// --output-limit-bytes: a rough cap on how much of the solutions a run
+// writes out. Once the next solution would take the output past it,
+// nothing more is written, and the search is cancelled just as Ctrl-C
+// cancels it (so a search still finding solutions stops too).
+//
+static LIMIT: OnceLock<u64> = OnceLock::new();
+static WRITTEN: AtomicU64 = AtomicU64::new(0);
+static TRUNCATED: AtomicBool = AtomicBool::new(false);
+
+pub fn set(bytes: u64) {
+    let _ = LIMIT.set(bytes);
+}
+
+pub fn limit() -> Option<u64> {
+    LIMIT.get().copied()
+}
+
+// count `bytes` more of output, returning whether they're still within
+// the limit (and so should be written)
+//
+pub fn take(bytes: usize) -> bool {
+    let Some(limit) = limit() else {
+        return true;
+    };
+
+    let written = WRITTEN.fetch_add(bytes as u64, Ordering::Relaxed) + bytes as u64;
+
+    if written <= limit && !truncated() {
+        return true;
+    }
+
+    if !TRUNCATED.swap(true, Ordering::Relaxed) {
+        interrupt::cancel_flag().store(true, Ordering::Relaxed);
+    }
+    false
+}
+
+// print a line, if it's within the limit
+//
+pub fn println(line: impl Display) {
+    if limit().is_none() {
+        println!("{}", line);
+        return;
+    }
+
+    let line = line.to_string();
+
+    if take(line.len() + 1) {
+        println!("{}", line);
+    }
+}
+
+// print a line, if it's within the limit, and flush it out straight
+// away (for --stream-immediate) rather than whenever stdout would
+//
+pub fn println_flushed(line: impl Display) {
+    let line = line.to_string();
+
+    if !take(line.len() + 1) {
+        return;
+    }
+
+    let mut out = io::stdout().lock();
+
+    if let Err(err) = writeln!(out, "{}", line).and_then(|()| out.flush()) {
+        eprintln!("error: can't write output: {}", err);
+        exit(2);
+    }
+}
+
+pub fn truncated() -> bool {
+    TRUNCATED.load(Ordering::Relaxed)
+}
+
+// say so on stderr if the limit cut the output short, returning
+// whether it did
+//
+pub fn report() -> bool {
+    if truncated() {
+        eprintln!(
+            "truncated: the output stopped at about {} bytes (--output-limit-bytes)",
+            limit().unwrap_or(0)
+        );
+    }
+    truncated()
+}
@@ -0,0 +1,122 @@
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::index::Index;
+use crate::mask::Mask;
+use crate::word::ZChar;
+
+/// How often the rarest letters turn up together in the candidate
+/// words, from [`Solver::letter_pairs`](crate::Solver::letter_pairs).
+/// Displays as a table, with each letter's own count on the diagonal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterPairs {
+    letters: Vec<ZChar>,
+    counts: Vec<usize>,
+    candidates: usize,
+}
+
+impl LetterPairs {
+    /// The letters compared, rarest first (the order the search gives
+    /// them their bits in).
+    pub fn letters(&self) -> &[ZChar] {
+        &self.letters
+    }
+
+    /// How many candidates contain both `a` and `b` (or, for the same
+    /// letter twice, just that letter). `None` unless both letters are
+    /// among the [`letters`](Self::letters).
+    pub fn count(&self, a: ZChar, b: ZChar) -> Option<usize> {
+        let at = |z: ZChar| self.letters.iter().position(|&l| l == z);
+
+        Some(self.counts[at(a)? * self.letters.len() + at(b)?])
+    }
+
+    /// The pairs of distinct letters no candidate contains both of.
+    pub fn never_together(&self) -> Vec<(ZChar, ZChar)> {
+        let n = self.letters.len();
+
+        (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| self.counts[i * n + j] == 0)
+            .map(|(i, j)| (self.letters[i], self.letters[j]))
+            .collect()
+    }
+}
+
+impl Display for LetterPairs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let n = self.letters.len();
+        let width = self
+            .counts
+            .iter()
+            .max()
+            .map_or(1, |max| max.to_string().len());
+
+        writeln!(
+            f,
+            "the {} rarest letters, over {} candidates:",
+            n, self.candidates
+        )?;
+
+        write!(f, "   ")?;
+        for letter in &self.letters {
+            write!(f, " {:>width$}", letter.chr(), width = width)?;
+        }
+        writeln!(f)?;
+
+        for (i, letter) in self.letters.iter().enumerate() {
+            write!(f, "  {}", letter)?;
+
+            // (the table is symmetric, so only its lower half is shown)
+            //
+            for count in &self.counts[i * n..=i * n + i] {
+                write!(f, " {:>width$}", count, width = width)?;
+            }
+            writeln!(f)?;
+        }
+
+        let never = self.never_together();
+
+        match never.is_empty() {
+            true => write!(f, "every pair turns up together"),
+            false => {
+                write!(f, "never together:")?;
+                for (a, b) in never {
+                    write!(f, " {}{}", a, b)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// count the candidates holding each pair of the `rarest` lowest bits
+// (the rarest letters, as the index orders them)
+//
+pub(crate) fn count<M: Mask>(index: &Index<M>, rarest: usize) -> LetterPairs {
+    let n = rarest.min(index.symbols as usize);
+    let mut counts = vec![0; n * n];
+
+    for &bits in index.word_lut.keys() {
+        for i in 0..n {
+            if bits & M::bit(i as u32) == M::ZERO {
+                continue;
+            }
+            for j in 0..n {
+                let pair = M::bit(i as u32) | M::bit(j as u32);
+
+                if bits & pair == pair {
+                    counts[i * n + j] += 1;
+                }
+            }
+        }
+    }
+
+    LetterPairs {
+        letters: index.bit_to_char[..n].to_vec(),
+        counts,
+        candidates: index.word_lut.len(),
+    }
+}
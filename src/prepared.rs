@@ -0,0 +1,310 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::ops::RangeInclusive;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+
+#[cfg(feature = "unicode")]
+use crate::normalize::Normalize;
+use crate::word::{ZChar, ZWord};
+
+// a dictionary word that could be a candidate for some search: its
+// (normalized) spelling, its letters, and their plain (bit 0 = 'a')
+// mask
+//
+pub(crate) struct Entry<'a> {
+    pub spelling: Cow<'a, str>,
+    pub word: ZWord,
+    pub bits: u64,
+}
+
+/// A dictionary read once, ready to be solved any number of times.
+///
+/// Preparing keeps every word made of distinct plain letters, of any
+/// length; each solve then only picks out the words its own
+/// constraints allow, so one prepared dictionary can answer many
+/// differently-configured [`Solver`](crate::Solver)s.
+pub struct PreparedDict<'a> {
+    pub(crate) entries: Vec<Entry<'a>>,
+}
+
+impl<'a> PreparedDict<'a> {
+    /// Prepare `dict` (one word per line, ignoring any whitespace
+    /// around it), using its words as they are.
+    pub fn new(dict: &'a str) -> Self {
+        Self::from_words(dict.lines())
+    }
+
+    /// Prepare `dict`, normalizing each word first.
+    #[cfg(feature = "unicode")]
+    pub fn normalized(dict: &'a str, normalize: Normalize) -> Self {
+        // composed and decomposed spellings of the same word should
+        // index identically, so settle on one form before anything
+        // else looks at the word
+        //
+        Self::build(dict.lines().map(|word| normalize.apply(word.trim())))
+    }
+
+    /// Prepare a dictionary as it's read from `reader`, a line at a
+    /// time, so only the usable words are held rather than the whole
+    /// text.
+    #[cfg(feature = "std")]
+    pub fn from_reader(reader: impl BufRead) -> io::Result<PreparedDict<'static>> {
+        PreparedDict::read(reader, |word| Cow::Borrowed(word))
+    }
+
+    // the words of each line from `reader`, as `normalize` leaves them,
+    // stopping at the first read error
+    //
+    #[cfg(feature = "std")]
+    pub(crate) fn read(
+        reader: impl BufRead,
+        normalize: impl Fn(&str) -> Cow<'_, str>,
+    ) -> io::Result<PreparedDict<'static>> {
+        let mut error = None;
+
+        let lines = reader
+            .lines()
+            .map_while(|line| line.map_err(|err| error = Some(err)).ok());
+        let dict =
+            PreparedDict::build(lines.map(|line| Cow::Owned(normalize(line.trim()).into_owned())));
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(dict),
+        }
+    }
+
+    /// Prepare `dict` from its raw bytes (a memory-mapped file, say),
+    /// checking that each line is valid UTF-8 as it's split off, so the
+    /// text is never copied whole.
+    pub fn from_bytes(dict: &'a [u8]) -> Result<Self, NotUtf8> {
+        Self::read_bytes(dict, Cow::Borrowed)
+    }
+
+    // the words of each line of `dict`, as `normalize` leaves them,
+    // stopping at the first that isn't UTF-8
+    //
+    pub(crate) fn read_bytes(
+        dict: &'a [u8],
+        normalize: impl Fn(&'a str) -> Cow<'a, str>,
+    ) -> Result<Self, NotUtf8> {
+        let mut error = None;
+
+        let lines = dict
+            .split(|&b| b == b'\n')
+            .enumerate()
+            .map_while(|(i, line)| {
+                core::str::from_utf8(line)
+                    .map_err(|_| error = Some(NotUtf8 { line: i + 1 }))
+                    .ok()
+            });
+        let dict = Self::build(lines.map(|line| normalize(line.trim())));
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(dict),
+        }
+    }
+
+    pub(crate) fn from_words(words: impl Iterator<Item = &'a str>) -> Self {
+        // stray whitespace around a word (trailing spaces, or the CR
+        // of a CRLF line ending) isn't part of it
+        //
+        Self::build(words.map(|word| Cow::Borrowed(word.trim())))
+    }
+
+    /// How many words could be candidates (before any constraints).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The usable words, in dictionary order (anagrams included).
+    pub fn words(&self) -> impl Iterator<Item = &ZWord> {
+        self.entries.iter().map(|entry| &entry.word)
+    }
+
+    /// The usable words, as in [`words`](Self::words), each with its
+    /// spelling in the dictionary: the same letters, but in the case
+    /// the dictionary gives them (and after any normalization).
+    pub fn spellings(&self) -> impl Iterator<Item = (&ZWord, &str)> {
+        self.entries
+            .iter()
+            .map(|entry| (&entry.word, &*entry.spelling))
+    }
+
+    fn build(words: impl Iterator<Item = Cow<'a, str>>) -> Self {
+        let mut entries = Vec::with_capacity(6000);
+
+        for spelling in words {
+            // blank lines (even ones holding only whitespace) aren't
+            // words at all
+            //
+            if spelling.is_empty() {
+                continue;
+            }
+
+            if let Ok((word, bits)) = read_letters(&spelling) {
+                entries.push(Entry {
+                    spelling,
+                    word,
+                    bits,
+                });
+            }
+        }
+
+        PreparedDict { entries }
+    }
+}
+
+/// The first line of a dictionary read from bytes that isn't valid
+/// UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotUtf8 {
+    /// The line number, counting from 1.
+    pub line: usize,
+}
+
+impl Display for NotUtf8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "line {} isn't valid UTF-8", self.line)
+    }
+}
+
+impl core::error::Error for NotUtf8 {}
+
+/// Why a dictionary line can't be used as a word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// It contains something other than a plain (ASCII) letter.
+    NotALetter(char),
+    /// It uses this letter more than once.
+    RepeatedLetter(char),
+    /// It has this many letters, rather than the word length.
+    WrongLength(usize),
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Problem::NotALetter(c) => write!(f, "{:?} isn't a plain letter", c),
+            Problem::RepeatedLetter(c) => write!(f, "'{}' is used more than once", c),
+            Problem::WrongLength(1) => write!(f, "it has 1 letter"),
+            Problem::WrongLength(len) => write!(f, "it has {} letters", len),
+        }
+    }
+}
+
+/// A line of a dictionary that
+/// [`Solver::validate`](crate::Solver::validate) found the solver
+/// would have to skip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedWord {
+    /// The line number, counting from 1.
+    pub line: usize,
+    pub word: String,
+    pub problem: Problem,
+    /// Which character of the word (counting from 1) the problem is:
+    /// the one that isn't a letter, or the second of a repeated
+    /// letter. `None` when the word is the wrong length.
+    pub position: Option<usize>,
+    /// The word lengths the solver wanted.
+    pub lengths: RangeInclusive<usize>,
+}
+
+impl MalformedWord {
+    pub(crate) fn new(
+        line: usize,
+        word: &str,
+        problem: Problem,
+        lengths: RangeInclusive<usize>,
+    ) -> Self {
+        let mut chars = word.chars().map(|c| c.to_ascii_lowercase());
+
+        let position = match problem {
+            Problem::NotALetter(c) => chars.position(|x| x == c),
+            Problem::RepeatedLetter(c) => chars
+                .position(|x| x == c)
+                .and_then(|first| chars.position(|x| x == c).map(|gap| first + gap + 1)),
+            Problem::WrongLength(_) => None,
+        };
+
+        MalformedWord {
+            line,
+            word: word.into(),
+            problem,
+            position: position.map(|i| i + 1),
+            lengths,
+        }
+    }
+}
+
+impl Display for MalformedWord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "line {}: {:?} ", self.line, self.word)?;
+
+        match (&self.problem, self.position) {
+            (Problem::NotALetter(c), Some(at)) => {
+                write!(
+                    f,
+                    "has {:?} at position {}, which isn't a plain letter",
+                    c, at
+                )
+            }
+            (Problem::RepeatedLetter(c), Some(at)) => {
+                write!(f, "repeats '{}' at position {}", c, at)
+            }
+            (Problem::WrongLength(len), _) => {
+                let (shortest, longest) = (self.lengths.start(), self.lengths.end());
+
+                write!(
+                    f,
+                    "has {} letter{}, not ",
+                    len,
+                    if *len == 1 { "" } else { "s" }
+                )?;
+                match shortest == longest {
+                    true => write!(f, "{}", shortest),
+                    false => write!(f, "{} to {}", shortest, longest),
+                }
+            }
+            (problem, None) => write!(f, "can't be used: {}", problem),
+        }
+    }
+}
+
+// the letters of a spelling, and their plain (bit 0 = 'a') mask, if
+// it's made of distinct plain letters
+//
+pub(crate) fn read_letters(spelling: &str) -> Result<(ZWord, u64), Problem> {
+    let mut bits = 0;
+    let mut word = ZWord::default();
+
+    for c in spelling.chars() {
+        // anything but a plain letter (punctuation, digits, or
+        // accented letters we haven't normalized away) can't be
+        // mapped onto the alphabet, bar the symbols past 'z'
+        //
+        let z = ZChar::try_from(c).map_err(Problem::NotALetter)?;
+        let b = z.mask();
+
+        // if we get a duplicate letter (e.g. floor - has two o's)
+        // this isn't a valid word as all letters MUST
+        // appear only ONCE
+        //
+        if bits & b != 0 {
+            return Err(Problem::RepeatedLetter(z.chr()));
+        }
+
+        bits |= b;
+        word.push(z);
+    }
+
+    Ok((word, bits))
+}
@@ -0,0 +1,56 @@
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use fivewords::{Progress, SolverBuilder};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+// --progress-bar: a bar on stderr of the branches (seeds) the search has
+// finished, with the time so far and the solutions found, redrawn as
+// each branch finishes (and the clock every so often between). When
+// stderr isn't a terminal there's no bar, so a log or a pipe gets
+// nothing extra.
+//
+pub fn attach(builder: SolverBuilder) -> SolverBuilder {
+    if !std::io::stderr().is_terminal() {
+        return builder;
+    }
+
+    let bar = ProgressBar::hidden();
+
+    bar.set_style(
+        ProgressStyle::with_template("{elapsed_precise} [{bar:40}] {pos}/{len} seeds, {msg}")
+            .expect("the template is valid")
+            .progress_chars("=> "),
+    );
+
+    builder.on_progress(move |progress: Progress| {
+        // (out of sight until it's set up, so it never shows as 0/0)
+        //
+        if progress.done == 0 {
+            bar.set_draw_target(ProgressDrawTarget::hidden());
+            bar.reset();
+        }
+
+        // (a thread's report can arrive after the last branch's)
+        //
+        if bar.is_finished() {
+            return;
+        }
+
+        bar.set_message(match progress.solutions {
+            1 => "1 solution".to_string(),
+            n => format!("{} solutions", n),
+        });
+        bar.set_length(progress.branches as u64);
+        bar.set_position(progress.done as u64);
+
+        // (each solve draws its own bar, so the last leaves none behind)
+        //
+        if progress.done == 0 {
+            bar.set_draw_target(ProgressDrawTarget::stderr());
+            bar.enable_steady_tick(Duration::from_millis(200));
+        } else if progress.done == progress.branches {
+            bar.finish_and_clear();
+        }
+    })
+}
@@ -0,0 +1,72 @@
+// the Python module, built with maturin (see pyproject.toml):
+//
+//   import fivewords
+//   fivewords.solve(words, allowed="", required="", excluded="",
+//                   length=5, count=5, skip=True, limit=None)
+//
+// returns each solution as a list of its words.
+//
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::SolverBuilder;
+
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (
+    words,
+    allowed = "",
+    required = "",
+    excluded = "",
+    length = 5,
+    count = 5,
+    skip = true,
+    limit = None,
+))]
+fn solve(
+    py: Python<'_>,
+    words: Vec<String>,
+    allowed: &str,
+    required: &str,
+    excluded: &str,
+    length: usize,
+    count: usize,
+    skip: bool,
+    limit: Option<usize>,
+) -> PyResult<Vec<Vec<String>>> {
+    let mut builder = SolverBuilder::new()
+        .word_len(length)
+        .set_size(count)
+        .allow_skip(skip)
+        .required_letters(required)
+        .excluded_letters(excluded);
+
+    // an empty string leaves the whole alphabet allowed
+    //
+    if !allowed.is_empty() {
+        builder = builder.allowed_letters(allowed);
+    }
+    if let Some(limit) = limit {
+        builder = builder.limit(limit);
+    }
+
+    let solver = builder
+        .build()
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    let dict = words.join("\n");
+
+    // the search can take a while, and never touches Python objects
+    //
+    let solutions = py.allow_threads(|| solver.solve(&dict));
+
+    Ok(solutions
+        .iter()
+        .map(|solution| solution.words().iter().map(|w| w.to_string()).collect())
+        .collect())
+}
+
+#[pymodule]
+fn fivewords(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve, m)?)
+}
@@ -0,0 +1,367 @@
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::event::Event;
+use crate::index::Index;
+use crate::mask::Mask;
+use crate::solution::Solution;
+
+// everything a search needs that doesn't change from call to call
+//
+pub(crate) struct Search<'a, M: Mask> {
+    pub index: &'a Index<M>,
+
+    // remapped letters every solution must cover, so we never skip them
+    //
+    pub required: M,
+
+    // set once we have all we asked for, or (from outside) once the
+    // caller has had enough, so every branch can wind down
+    //
+    pub stop: &'a AtomicBool,
+    pub cancel: &'a AtomicBool,
+
+    // when a time budget runs out, and whether any branch has noticed
+    // it has
+    //
+    #[cfg(feature = "std")]
+    pub deadline: Option<std::time::Instant>,
+    pub expired: &'a AtomicBool,
+    pub emit: &'a Emit<'a, M>,
+    pub events: Option<&'a (dyn Fn(&Event) + Sync)>,
+
+    // the (debug build) trace only covers branches shallower than this
+    //
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub trace_depth: usize,
+}
+
+// hands on a branch's solution (by its seed and its words), or keeps
+// it in the branch's list
+//
+pub(crate) type Emit<'a, M> = dyn Fn(u32, &[M], &mut Vec<Solution>) + Sync + 'a;
+
+// what a branch keeps to itself as it's searched: how many nodes it
+// has visited, and (when the solve collects its solutions rather than
+// handing them on) the solutions it has found, so the threads never
+// share a lock as they go
+//
+#[derive(Default)]
+pub(crate) struct Tally {
+    pub visits: u64,
+    pub found: Vec<Solution>,
+}
+
+// how many visits a thread makes between looks at the clock: often
+// enough to stop well within a millisecond of the deadline, and rarely
+// enough that reading the clock costs next to nothing
+//
+#[cfg(feature = "std")]
+const CLOCK_EVERY: u64 = 4096;
+
+impl<M: Mask> Search<'_, M> {
+    #[inline]
+    pub fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+            || self.cancel.load(Ordering::Relaxed)
+            || self.expired.load(Ordering::Relaxed)
+    }
+
+    // hand an event to the event log, only building it if there is one
+    //
+    #[inline]
+    pub fn log(&self, event: impl FnOnce() -> Event) {
+        if let Some(events) = self.events {
+            events(&event());
+        }
+    }
+
+    fn solution(&self, selected: &[M]) -> Event {
+        Event::Solution {
+            words: selected
+                .iter()
+                .map(|bits| self.index.word_lut[bits])
+                .collect(),
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    pub fn check_clock(&self, visits: u64) {
+        #[cfg(feature = "std")]
+        if let Some(deadline) = self.deadline {
+            if visits.is_multiple_of(CLOCK_EVERY) && std::time::Instant::now() >= deadline {
+                self.expired.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+// do the search, trying to fill our first free bit in our
+// final 'mask', using the LSB lookups. While we still have `skips`
+// spare letters, we may also leave the lowest free bit empty, as
+// long as it lies above the letter skipped by the branch's `seed`.
+//
+pub(crate) fn search<M: Mask>(
+    ctx: &Search<M>,
+    selected: &mut [M],
+    tally: &mut Tally,
+    mask: M,
+    depth: usize,
+    skips: usize,
+    seed: u32,
+) {
+    tally.visits += 1;
+    ctx.check_clock(tally.visits);
+
+    if ctx.stopped() {
+        return;
+    }
+
+    if depth == selected.len() {
+        // the seed must be the LOWEST unused letter, or a solution with
+        // several unused letters would be found once per seed. Letters
+        // below the seed are never skipped, but they can still be left
+        // free when the last word fills a lower bit.
+        //
+        let below_seed = M::low(seed);
+
+        if mask & below_seed == below_seed {
+            ctx.log(|| ctx.solution(selected));
+            (ctx.emit)(seed, selected, &mut tally.found);
+        }
+        return;
+    }
+
+    // find the lowest free bit (next low-frequency character)
+    //
+    let lowbit = mask.trailing_ones();
+
+    // each word still to place has its own (lowest) free bit, so if
+    // fewer free bits than that have any words for them, this branch
+    // can't be finished
+    //
+    if lowbit >= ctx.index.symbols
+        || ((!mask & ctx.index.filled).count_ones() as usize) < selected.len() - depth
+    {
+        ctx.log(|| Event::Prune { depth });
+        return;
+    }
+
+    let words = ctx.index.bucket(lowbit);
+
+    ctx.log(|| Event::Enter {
+        depth,
+        letter: ctx.index.bit_to_char[lowbit as usize],
+        words: words.len(),
+    });
+
+    #[cfg(feature = "std")]
+    if cfg!(debug_assertions) && depth < ctx.trace_depth {
+        println!(
+            "free lowbit [{:#02}] with mask [{:#028b}] at depth {} :: searching {} words...",
+            lowbit,
+            mask.widen(),
+            depth,
+            words.len()
+        );
+    }
+
+    for &bits in words {
+        if mask & bits == M::ZERO {
+            ctx.log(|| Event::Try {
+                depth,
+                word: ctx.index.word_lut[&bits],
+            });
+
+            selected[depth] = bits;
+            search(ctx, selected, tally, mask | bits, depth + 1, skips, seed);
+        }
+    }
+
+    let lowmask = M::bit(lowbit);
+
+    if skips > 0 && lowbit > seed && ctx.required & lowmask == M::ZERO {
+        ctx.log(|| Event::Skip {
+            depth,
+            letter: ctx.index.bit_to_char[lowbit as usize],
+        });
+
+        search(ctx, selected, tally, mask | lowmask, depth, skips - 1, seed);
+    }
+}
+
+// where the iterative search is in one call of `search`: the branch's
+// mask, depth and skips, its lowest free bit, and the next word of
+// that bit's bucket to try
+//
+struct Frame<M> {
+    mask: M,
+    depth: usize,
+    skips: usize,
+    lowbit: u32,
+    next: usize,
+}
+
+// the same search as `search`, keeping its calls on an explicit stack
+// rather than the thread's, so how deep it goes is only limited by
+// memory. Solutions are found in the same order.
+//
+pub(crate) fn search_iter<M: Mask>(
+    ctx: &Search<M>,
+    selected: &mut [M],
+    tally: &mut Tally,
+    mask: M,
+    depth: usize,
+    skips: usize,
+    seed: u32,
+) {
+    let mut stack = Vec::with_capacity(selected.len() + skips + 1);
+
+    tally.visits += 1;
+    stack.extend(enter(
+        ctx,
+        selected,
+        &mut tally.found,
+        mask,
+        depth,
+        skips,
+        seed,
+    ));
+
+    while let Some(frame) = stack.last_mut() {
+        ctx.check_clock(tally.visits);
+
+        if ctx.stopped() {
+            return;
+        }
+
+        let words = ctx.index.bucket(frame.lowbit);
+        let mask = frame.mask;
+
+        match words[frame.next..]
+            .iter()
+            .position(|&bits| mask & bits == M::ZERO)
+        {
+            Some(offset) => {
+                let bits = words[frame.next + offset];
+                let (depth, skips) = (frame.depth, frame.skips);
+
+                frame.next += offset + 1;
+                selected[depth] = bits;
+
+                ctx.log(|| Event::Try {
+                    depth,
+                    word: ctx.index.word_lut[&bits],
+                });
+
+                tally.visits += 1;
+                stack.extend(enter(
+                    ctx,
+                    selected,
+                    &mut tally.found,
+                    mask | bits,
+                    depth + 1,
+                    skips,
+                    seed,
+                ));
+            }
+            None => {
+                // the bucket is done, which leaves (at most) the branch
+                // skipping this bit, and that replaces this frame
+                //
+                let Frame {
+                    mask,
+                    depth,
+                    skips,
+                    lowbit,
+                    ..
+                } = stack.pop().unwrap();
+                let lowmask = M::bit(lowbit);
+
+                if skips > 0 && lowbit > seed && ctx.required & lowmask == M::ZERO {
+                    ctx.log(|| Event::Skip {
+                        depth,
+                        letter: ctx.index.bit_to_char[lowbit as usize],
+                    });
+
+                    tally.visits += 1;
+                    stack.extend(enter(
+                        ctx,
+                        selected,
+                        &mut tally.found,
+                        mask | lowmask,
+                        depth,
+                        skips - 1,
+                        seed,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+// what `search` does on entry: emit a complete solution, or give the
+// frame to fill the lowest free bit
+//
+fn enter<M: Mask>(
+    ctx: &Search<M>,
+    selected: &[M],
+    found: &mut Vec<Solution>,
+    mask: M,
+    depth: usize,
+    skips: usize,
+    seed: u32,
+) -> Option<Frame<M>> {
+    if ctx.stopped() {
+        return None;
+    }
+
+    if depth == selected.len() {
+        // as in `search`, the seed must be the lowest unused letter
+        //
+        let below_seed = M::low(seed);
+
+        if mask & below_seed == below_seed {
+            ctx.log(|| ctx.solution(selected));
+            (ctx.emit)(seed, selected, found);
+        }
+        return None;
+    }
+
+    let lowbit = mask.trailing_ones();
+
+    // as in `search`, prune branches without enough filled buckets
+    //
+    if lowbit >= ctx.index.symbols
+        || ((!mask & ctx.index.filled).count_ones() as usize) < selected.len() - depth
+    {
+        ctx.log(|| Event::Prune { depth });
+        return None;
+    }
+
+    ctx.log(|| Event::Enter {
+        depth,
+        letter: ctx.index.bit_to_char[lowbit as usize],
+        words: ctx.index.bucket(lowbit).len(),
+    });
+
+    #[cfg(feature = "std")]
+    if cfg!(debug_assertions) && depth < ctx.trace_depth {
+        println!(
+            "free lowbit [{:#02}] with mask [{:#028b}] at depth {} :: searching {} words...",
+            lowbit,
+            mask.widen(),
+            depth,
+            ctx.index.bucket(lowbit).len()
+        );
+    }
+
+    Some(Frame {
+        mask,
+        depth,
+        skips,
+        lowbit,
+        next: 0,
+    })
+}
@@ -0,0 +1,189 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::index::Index;
+use crate::mask::Mask;
+use crate::shim::Set;
+
+/// One of the invariants [`Solver::self_test`](crate::Solver::self_test)
+/// checks the search's index against, and what it found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    /// What the check verifies.
+    pub name: &'static str,
+    /// The first violation found, if any.
+    pub result: Result<(), String>,
+}
+
+// check an index built with masks of width `M`
+//
+pub(crate) fn run<M: Mask>(index: &Index<M>, lengths: RangeInclusive<usize>) -> Vec<Check> {
+    vec![
+        Check {
+            name: "every candidate's mask has one bit per letter",
+            result: mask_widths(index, lengths),
+        },
+        Check {
+            name: "letters map one-to-one onto the mask bits",
+            result: bijection(index),
+        },
+        Check {
+            name: "the buckets hold exactly the candidates, by lowest bit",
+            result: buckets(index),
+        },
+        Check {
+            name: "every mask decodes back to its word",
+            result: round_trip(index),
+        },
+    ]
+}
+
+// the same dictionary indexed with a narrower and a wider mask should
+// give the same bits, just in a wider integer
+//
+pub(crate) fn widths_agree(narrow: &Index<u32>, wide: &Index<u64>) -> Check {
+    let result = match narrow
+        .bit_to_char
+        .iter()
+        .zip(&wide.bit_to_char)
+        .position(|(a, b)| a != b)
+    {
+        Some(bit) => Err(format!(
+            "bit {} is '{}' in a u32 mask but '{}' in a u64",
+            bit, narrow.bit_to_char[bit], wide.bit_to_char[bit]
+        )),
+        None if narrow.word_lut.len() != wide.word_lut.len() => Err(format!(
+            "{} candidates with u32 masks, but {} with u64",
+            narrow.word_lut.len(),
+            wide.word_lut.len()
+        )),
+        None => narrow
+            .word_lut
+            .keys()
+            .find(|bits| !wide.word_lut.contains_key(&bits.widen()))
+            .map_or(Ok(()), |bits| {
+                Err(format!("{:#x} has no u64 counterpart", bits))
+            }),
+    };
+
+    Check {
+        name: "u32 and u64 masks agree",
+        result,
+    }
+}
+
+fn mask_widths<M: Mask>(index: &Index<M>, lengths: RangeInclusive<usize>) -> Result<(), String> {
+    for (&bits, word) in &index.word_lut {
+        if bits.count_ones() as usize != word.len() || !lengths.contains(&word.len()) {
+            return Err(format!(
+                "'{}' has {} bits set, for {} letters",
+                word,
+                bits.count_ones(),
+                word.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn bijection<M: Mask>(index: &Index<M>) -> Result<(), String> {
+    let mut covered = M::ZERO;
+
+    for (letter, &(bits, bit)) in index.mask_lut.iter().enumerate() {
+        if bits != M::bit(bit as u32) {
+            return Err(format!(
+                "letter {} has mask {:#x} but bit {}",
+                letter,
+                bits.widen(),
+                bit
+            ));
+        }
+        if covered & bits != M::ZERO {
+            return Err(format!("bit {} is given to two letters", bit));
+        }
+        if index.bit_to_char[bit].ord() != letter {
+            return Err(format!(
+                "bit {} maps back to '{}', not letter {}",
+                bit, index.bit_to_char[bit], letter
+            ));
+        }
+
+        covered |= bits;
+    }
+
+    match covered == M::low(index.symbols) {
+        true => Ok(()),
+        false => Err(format!("only bits {:#x} are used", covered.widen())),
+    }
+}
+
+fn buckets<M: Mask>(index: &Index<M>) -> Result<(), String> {
+    let mut held = Set::new();
+
+    for lowbit in 0..index.symbols {
+        let bucket = index.bucket(lowbit);
+
+        if bucket.is_empty() == (index.filled & M::bit(lowbit) != M::ZERO) {
+            return Err(format!(
+                "bucket {} is wrongly marked as filled or empty",
+                lowbit
+            ));
+        }
+
+        for bits in bucket {
+            if bits.trailing_zeros() != lowbit {
+                return Err(format!(
+                    "{:#x} is in bucket {}, but its lowest bit is {}",
+                    bits.widen(),
+                    lowbit,
+                    bits.trailing_zeros()
+                ));
+            }
+            if !index.word_lut.contains_key(bits) {
+                return Err(format!(
+                    "{:#x} is in bucket {} but has no word",
+                    bits.widen(),
+                    lowbit
+                ));
+            }
+            if !held.insert(*bits) {
+                return Err(format!("{:#x} is held twice", bits.widen()));
+            }
+        }
+    }
+
+    // every bucketed mask has a word, so with as many masks as words
+    // they are the same set
+    //
+    match held.len() == index.word_lut.len() {
+        true => Ok(()),
+        false => Err(format!(
+            "the buckets hold {} masks, for {} candidates",
+            held.len(),
+            index.word_lut.len()
+        )),
+    }
+}
+
+fn round_trip<M: Mask>(index: &Index<M>) -> Result<(), String> {
+    for (&bits, word) in &index.word_lut {
+        let raw = word.iter().fold(0, |acc, z| acc | z.mask());
+
+        let mut letters: Vec<_> = word.iter().map(|z| z.ord()).collect();
+        letters.sort_unstable();
+
+        let decoded: Vec<_> = index.decode(bits).iter().map(|z| z.ord()).collect();
+
+        if decoded != letters || index.remap(raw) != bits {
+            return Err(format!(
+                "'{}' doesn't survive a round trip through {:#x}",
+                word,
+                bits.widen()
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+// the few things the solver needs beyond `core` and `alloc`: hashed
+// collections, a lock for the solution lists, and a clock for the
+// stats. With `std` we use its own; without it we fall back on the
+// ordered collections, a spin lock (the search then runs on the one
+// thread, so it never actually spins), and timings of zero.
+//
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map::Entry, HashMap as Map, HashSet as Set};
+#[cfg(feature = "std")]
+pub(crate) use std::sync::Mutex;
+#[cfg(feature = "std")]
+pub(crate) use std::time::Instant;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::{btree_map::Entry, BTreeMap as Map, BTreeSet as Set};
+#[cfg(not(feature = "std"))]
+pub(crate) use no_std::{Instant, Mutex};
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use core::cell::UnsafeCell;
+    use core::convert::Infallible;
+    use core::ops::{Deref, DerefMut};
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use core::time::Duration;
+
+    pub(crate) struct Mutex<T> {
+        locked: AtomicBool,
+        value: UnsafeCell<T>,
+    }
+
+    // only the holder of the lock ever touches the value
+    //
+    unsafe impl<T: Send> Sync for Mutex<T> {}
+
+    impl<T> Mutex<T> {
+        pub fn new(value: T) -> Self {
+            Mutex {
+                locked: AtomicBool::new(false),
+                value: UnsafeCell::new(value),
+            }
+        }
+
+        pub fn lock(&self) -> Result<Guard<'_, T>, Infallible> {
+            while self
+                .locked
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                core::hint::spin_loop();
+            }
+
+            Ok(Guard { mutex: self })
+        }
+
+        pub fn into_inner(self) -> Result<T, Infallible> {
+            Ok(self.value.into_inner())
+        }
+    }
+
+    impl<T: Default> Default for Mutex<T> {
+        fn default() -> Self {
+            Mutex::new(T::default())
+        }
+    }
+
+    pub(crate) struct Guard<'a, T> {
+        mutex: &'a Mutex<T>,
+    }
+
+    impl<T> Deref for Guard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            unsafe { &*self.mutex.value.get() }
+        }
+    }
+
+    impl<T> DerefMut for Guard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            unsafe { &mut *self.mutex.value.get() }
+        }
+    }
+
+    impl<T> Drop for Guard<'_, T> {
+        fn drop(&mut self) {
+            self.mutex.locked.store(false, Ordering::Release);
+        }
+    }
+
+    // a clock that never moves, as there's no clock to read
+    //
+    #[derive(Clone, Copy)]
+    pub(crate) struct Instant;
+
+    impl Instant {
+        pub fn now() -> Self {
+            Instant
+        }
+
+        pub fn elapsed(&self) -> Duration {
+            Duration::ZERO
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::word::{ZChar, ZWord};
+
+/// A set of words that share no letters.
+#[derive(Clone)]
+pub struct Solution {
+    pub(crate) words: Vec<ZWord>,
+    pub(crate) unused: Vec<ZChar>,
+    pub(crate) masks: Vec<u64>,
+}
+
+impl Solution {
+    /// The words, in the order the search picked them.
+    pub fn words(&self) -> &[ZWord] {
+        &self.words
+    }
+
+    /// The letters of the alphabet none of the words use, in
+    /// alphabetical order.
+    pub fn unused(&self) -> &[ZChar] {
+        &self.unused
+    }
+
+    /// The search's internal bitmask for each word, in the same order
+    /// as [`words`](Self::words). Bits are assigned by letter frequency
+    /// in the dictionary, rarest-first, so these are only meaningful
+    /// for debugging the remapping.
+    pub fn masks(&self) -> &[u64] {
+        &self.masks
+    }
+
+    /// A stable identifier for the set of letter sets this solution
+    /// uses: the same whatever order the words were picked in, which
+    /// anagram spelled each of them, or which run (or dictionary)
+    /// found it.
+    pub fn id(&self) -> u64 {
+        let mut sets: Vec<u64> = self
+            .words
+            .iter()
+            .map(|word| word.iter().fold(0, |acc, z| acc | z.mask()))
+            .collect();
+
+        sets.sort_unstable();
+
+        // FNV-1a, which (unlike the std hashers) is the same across
+        // runs, platforms and compiler versions. (a set of the first 32
+        // symbols is hashed as four bytes, so a set of letters has the
+        // same id however wide the alphabet)
+        //
+        sets.iter()
+            .flat_map(|set| {
+                let bytes = if set >> 32 == 0 { 4 } else { 8 };
+
+                set.to_le_bytes().into_iter().take(bytes)
+            })
+            .fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+                (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, word) in self.words.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", word)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1742 @@
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use crate::coverage::Cover;
+use crate::event::Event;
+use crate::explain::{self, Explanation};
+use crate::index::{Index, ALPHABET_LEN, ALPHABET_MASK};
+use crate::mask::Mask;
+#[cfg(feature = "unicode")]
+use crate::normalize::Normalize;
+use crate::pairs::{self, LetterPairs};
+use crate::prepared::{read_letters, MalformedWord, NotUtf8, PreparedDict, Problem};
+use crate::search::{search, search_iter, Search, Tally};
+use crate::selftest::{self, Check};
+use crate::shim::{Instant, Mutex, Set};
+use crate::solution::Solution;
+use crate::word::{ZChar, ZWord, MAX_SYMBOLS};
+
+/// The order [`Solver::solve`] returns solutions in.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Order {
+    /// The order the search found them in, branch (seed) by branch,
+    /// which is the same from run to run.
+    #[default]
+    Found,
+    /// Sorted by their words, as printed.
+    Alphabetical,
+    /// Sorted by their unused letters, then by their words, so the
+    /// solutions leaving out each letter are listed together.
+    Unused,
+}
+
+impl FromStr for Order {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "found" => Ok(Order::Found),
+            "alpha" => Ok(Order::Alphabetical),
+            "unused" => Ok(Order::Unused),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The order the search starts its branches (seeds) in. It changes
+/// which solutions turn up first, and how evenly the threads share the
+/// work, but never which solutions are found.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SeedOrder {
+    /// Rarest skipped letter first.
+    #[default]
+    Natural,
+    /// The branches with the most candidates to search first, so the
+    /// longest aren't left until last.
+    SizeDesc,
+    /// The branches with the fewest candidates first.
+    SizeAsc,
+}
+
+impl FromStr for SeedOrder {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "natural" => Ok(SeedOrder::Natural),
+            "size-desc" => Ok(SeedOrder::SizeDesc),
+            "size-asc" => Ok(SeedOrder::SizeAsc),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How the search walks its tree of partial solutions. Both find the
+/// same solutions, in the same order.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Backend {
+    /// Recursing once per word placed (or letter skipped), which is
+    /// the quickest for the usual set sizes.
+    #[default]
+    Recursive,
+    /// Keeping its place on an explicit, heap-allocated stack, so that
+    /// deep searches (large set sizes over a large alphabet) don't
+    /// depend on the size of the threads' stacks.
+    Iterative,
+}
+
+impl FromStr for Backend {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "recursive" => Ok(Backend::Recursive),
+            "iterative" => Ok(Backend::Iterative),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Which dictionary words count as distinct candidates.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Dedup {
+    /// One candidate per set of letters, spelled as the dictionary
+    /// first spells it, so anagrams never multiply the solutions.
+    #[default]
+    Bitmask,
+    /// One candidate per spelling: a solution is reported once for
+    /// every way of spelling its words. The spellings of a solution
+    /// still share one [`Solution::id`].
+    Original,
+}
+
+impl FromStr for Dedup {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bitmask" => Ok(Dedup::Bitmask),
+            "original" => Ok(Dedup::Original),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Which spelling stands for a set of letters the dictionary spells
+/// more than one way, when anagrams are collapsed.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Anagram {
+    /// The dictionary's first spelling.
+    #[default]
+    First,
+    /// The alphabetically first spelling, whatever order the
+    /// dictionary lists them in.
+    Alpha,
+}
+
+impl FromStr for Anagram {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first" => Ok(Anagram::First),
+            "alpha" => Ok(Anagram::Alpha),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Why a [`SolverBuilder`] could not build a [`Solver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The word length or set size was zero.
+    ZeroSize,
+    /// The range of word lengths was empty.
+    EmptyLengthRange,
+    /// The words need more distinct letters than are available.
+    TooFewLetters { needed: usize, available: usize },
+    /// A letter set contained something other than a letter.
+    InvalidLetter(char),
+    /// A required letter was also excluded (or not allowed).
+    RequiredUnavailable(char),
+    /// The words can't cover as many letters as
+    /// [`min_unique`](SolverBuilder::min_unique) asks, at most `most`.
+    CoverageOutOfReach { needed: usize, most: usize },
+    /// The [`unique_range`](SolverBuilder::unique_range) of letters to
+    /// cover was empty.
+    EmptyUniqueRange,
+    /// The search was given no threads to run on.
+    ZeroThreads,
+    /// Each word was allowed no spellings at all.
+    ZeroSpellings,
+    /// The word every solution must include can't be a word of one.
+    UnusableWord(Problem),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::ZeroSize => write!(f, "word length and set size must be at least 1"),
+            Error::EmptyLengthRange => write!(f, "the range of word lengths is empty"),
+            Error::TooFewLetters { needed, available } => write!(
+                f,
+                "the words need {} distinct letters, but only {} are available",
+                needed, available
+            ),
+            Error::InvalidLetter(c) => write!(f, "'{}' is not a letter", c),
+            Error::RequiredUnavailable(c) => write!(f, "required letter '{}' is not allowed", c),
+            Error::CoverageOutOfReach { needed, most } => write!(
+                f,
+                "the words can cover at most {} letters, not {}",
+                most, needed
+            ),
+            Error::EmptyUniqueRange => write!(f, "the range of letters to cover is empty"),
+            Error::ZeroThreads => write!(f, "the search needs at least 1 thread"),
+            Error::ZeroSpellings => write!(f, "each word needs at least 1 spelling"),
+            Error::UnusableWord(problem) => {
+                write!(f, "the word to include is unusable: {}", problem)
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// Identifies one of the independent branches the search is split
+/// into: the letter the branch skips first, or `None` for the single
+/// branch of a search that has no letters to skip.
+pub type Seed = Option<ZChar>;
+
+/// Counts and timings from a single solve.
+#[derive(Default, Clone, Debug)]
+pub struct Stats {
+    /// Distinct candidate words: one per anagram set, unless
+    /// [`Dedup::Original`] keeps every spelling.
+    pub candidates: usize,
+    /// Solutions passed on to the caller.
+    pub solutions: usize,
+    /// Nodes of the search tree visited (partial solutions tried).
+    pub visits: u64,
+    /// Whether the [`cancel_flag`](SolverBuilder::cancel_flag) cut
+    /// the search short, so the solutions are only some of them.
+    pub cancelled: bool,
+    /// Whether the search ran out of its
+    /// [`time_budget`](SolverBuilder::time_budget), so the solutions
+    /// are only some of them.
+    pub timed_out: bool,
+    pub index_time: Duration,
+    pub search_time: Duration,
+}
+
+/// How far a search has got, as handed to
+/// [`on_progress`](SolverBuilder::on_progress).
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Progress {
+    /// The branches (seeds) searched to the end, or cut short.
+    pub done: usize,
+    /// The branches the search has in all.
+    pub branches: usize,
+    /// The solutions found so far.
+    pub solutions: usize,
+}
+
+pub(crate) type Filter = Arc<dyn Fn(&Solution) -> bool + Send + Sync>;
+pub(crate) type EventLog = Arc<dyn Fn(&Event) + Send + Sync>;
+pub(crate) type ProgressFn = Arc<dyn Fn(Progress) + Send + Sync>;
+
+// the branch of a search with spare letters that skips none of them,
+// numbered after the seeds that skip a letter (of any alphabet)
+//
+pub(crate) const WHOLE: u32 = MAX_SYMBOLS as u32;
+
+#[derive(Clone)]
+pub(crate) struct Config {
+    // the words' length, or the shortest and longest they may be
+    //
+    pub word_len: usize,
+    pub max_word_len: usize,
+    pub set_size: usize,
+    pub allow_skip: bool,
+
+    // masks over the plain alphabet (bit 0 = 'a')
+    //
+    pub allowed: u64,
+    pub required: u64,
+    pub excluded: u64,
+    pub disjoint: u64,
+
+    // the word every solution must include, as its plain mask
+    //
+    pub include: Option<u64>,
+
+    pub limit: Option<usize>,
+    pub per_letter_limit: Option<usize>,
+    pub order: Order,
+    pub seed_order: SeedOrder,
+    pub backend: Backend,
+    pub dedup: Dedup,
+    pub anagram: Anagram,
+    pub max_spellings: Option<usize>,
+    pub trace_depth: Option<usize>,
+    pub intersect: Option<Set<String>>,
+
+    // (lowercase) letter pairs no word's spelling may contain
+    //
+    pub forbidden: Vec<String>,
+
+    // how many distinct letters a set's words must cover between them,
+    // when they may share letters, and how many they may
+    //
+    pub min_unique: Option<usize>,
+    pub max_unique: usize,
+
+    // letter counts (bit 0 = 'a') to order the remapped bits by, in
+    // place of the candidates' own
+    //
+    pub letter_freq: Option<[u32; MAX_SYMBOLS]>,
+
+    // the embedder's own rule every solution must pass, and a flag of
+    // theirs to stop the search early
+    //
+    pub filter: Option<Filter>,
+    pub cancel: Option<Arc<AtomicBool>>,
+
+    // where the search reports each step it takes
+    //
+    pub events: Option<EventLog>,
+    pub progress: Option<ProgressFn>,
+
+    // search on this many threads of our own, each with a fixed share
+    // of the seeds, rather than on rayon's
+    //
+    #[cfg(feature = "std")]
+    pub threads: Option<usize>,
+
+    // a pool the caller keeps warm across solves, to search on instead
+    // of rayon's global one (or threads spawned for each solve)
+    //
+    #[cfg(feature = "std")]
+    pub pool: Option<Arc<rayon::ThreadPool>>,
+
+    // how long a solve may run before it stops where it is
+    //
+    #[cfg(feature = "std")]
+    pub time_budget: Option<Duration>,
+    pub require_anagrammable: bool,
+    pub require_vowel: bool,
+    pub y_vowel: bool,
+    #[cfg(feature = "unicode")]
+    pub normalize: Normalize,
+}
+
+impl Config {
+    // the letters solutions are made from, and report as unused
+    //
+    pub fn alphabet(&self) -> u64 {
+        self.allowed & !self.excluded
+    }
+
+    // how many symbols the alphabet spans: the 26 letters, or as many
+    // as reach the last of any symbols past 'z'
+    //
+    pub fn symbols(&self) -> usize {
+        ALPHABET_LEN.max(64 - self.alphabet().leading_zeros() as usize)
+    }
+
+    // whether the alphabet is too large for the search's u32 masks
+    //
+    pub fn wide(&self) -> bool {
+        self.symbols() > 32
+    }
+
+    // the letters the words may actually use: the alphabet, less the
+    // letters solutions must stay disjoint from (which are still part
+    // of it, but always among the unused ones)
+    //
+    pub fn usable(&self) -> u64 {
+        self.alphabet() & !self.disjoint
+    }
+
+    // the letters counted as vowels (over the plain alphabet)
+    //
+    pub fn vowels(&self) -> u64 {
+        let vowels = "aeiou"
+            .chars()
+            .fold(0, |mask, c| mask | ZChar::from(c).mask());
+
+        match self.y_vowel {
+            true => vowels | ZChar::from('y').mask(),
+            false => vowels,
+        }
+    }
+
+    // the first forbidden bigram in `spelling`, if it has any
+    //
+    pub fn forbidden_in(&self, spelling: &str) -> Option<&str> {
+        if self.forbidden.is_empty() {
+            return None;
+        }
+
+        let spelling = spelling.to_ascii_lowercase();
+
+        self.forbidden
+            .iter()
+            .find(|bigram| spelling.contains(bigram.as_str()))
+            .map(String::as_str)
+    }
+
+    // the lengths a candidate may have
+    //
+    pub fn lengths(&self) -> RangeInclusive<usize> {
+        self.word_len..=self.max_word_len
+    }
+
+    // how many of the usable letters every solution leaves unused (at
+    // most, with words of several lengths)
+    //
+    pub fn spare_letters(&self) -> usize {
+        (self.usable().count_ones() as usize).saturating_sub(self.word_len * self.set_size)
+    }
+}
+
+/// Configures a [`Solver`]. The defaults solve the classic puzzle:
+/// five five-letter words, leaving one letter of the alphabet unused.
+#[derive(Clone)]
+pub struct SolverBuilder {
+    config: Config,
+    allowed: Option<String>,
+    required: String,
+    excluded: String,
+    disjoint: String,
+    include: Option<String>,
+}
+
+impl Default for SolverBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SolverBuilder {
+    pub fn new() -> Self {
+        SolverBuilder {
+            config: Config {
+                word_len: 5,
+                max_word_len: 5,
+                set_size: 5,
+                allow_skip: true,
+                allowed: ALPHABET_MASK,
+                required: 0,
+                excluded: 0,
+                disjoint: 0,
+                include: None,
+                limit: None,
+                per_letter_limit: None,
+                order: Order::Found,
+                seed_order: SeedOrder::Natural,
+                backend: Backend::Recursive,
+                dedup: Dedup::Bitmask,
+                anagram: Anagram::First,
+                max_spellings: None,
+                trace_depth: None,
+                intersect: None,
+                forbidden: Vec::new(),
+                min_unique: None,
+                max_unique: MAX_SYMBOLS,
+                letter_freq: None,
+                filter: None,
+                cancel: None,
+                events: None,
+                progress: None,
+                #[cfg(feature = "std")]
+                threads: None,
+                #[cfg(feature = "std")]
+                pool: None,
+                #[cfg(feature = "std")]
+                time_budget: None,
+                require_anagrammable: false,
+                require_vowel: false,
+                y_vowel: false,
+                #[cfg(feature = "unicode")]
+                normalize: Normalize::None,
+            },
+            allowed: None,
+            required: String::new(),
+            excluded: String::new(),
+            disjoint: String::new(),
+            include: None,
+        }
+    }
+
+    /// The length of every word (default 5).
+    pub fn word_len(mut self, len: usize) -> Self {
+        self.config.word_len = len;
+        self.config.max_word_len = len;
+        self
+    }
+
+    /// Let the words have any of several lengths, rather than the one
+    /// [`word_len`](Self::word_len), so a solution may mix them (say a
+    /// six-letter word with four of five). The solution's words then
+    /// leave however many letters unused their lengths come to, and
+    /// may well leave none.
+    pub fn word_len_range(mut self, lengths: RangeInclusive<usize>) -> Self {
+        self.config.word_len = *lengths.start();
+        self.config.max_word_len = *lengths.end();
+        self
+    }
+
+    /// How many words make up a solution (default 5).
+    pub fn set_size(mut self, size: usize) -> Self {
+        self.config.set_size = size;
+        self
+    }
+
+    /// Whether solutions may leave letters unused (default true). When
+    /// false, solutions must use every available letter.
+    pub fn allow_skip(mut self, allow: bool) -> Self {
+        self.config.allow_skip = allow;
+        self
+    }
+
+    /// Restrict the words to these letters (default: all 26 of them).
+    /// Naming any of the symbols past 'z' (see [`ZChar`]) makes them
+    /// part of the alphabet too, and one of more than 32 symbols is
+    /// searched with 64-bit masks rather than 32-bit ones.
+    pub fn allowed_letters(mut self, letters: &str) -> Self {
+        self.allowed = Some(letters.to_string());
+        self
+    }
+
+    /// Letters every solution must use.
+    pub fn required_letters(mut self, letters: &str) -> Self {
+        self.required = letters.to_string();
+        self
+    }
+
+    /// Letters no word may use.
+    pub fn excluded_letters(mut self, letters: &str) -> Self {
+        self.excluded = letters.to_string();
+        self
+    }
+
+    /// Letters every solution must leave unused, say because another
+    /// puzzle already uses them. This finds the same word sets as
+    /// excluding them would, but the letters stay in the alphabet: they
+    /// count against its spare letters (so each solution leaves exactly
+    /// as many letters unused as without them), and are listed among
+    /// each solution's unused letters. Excluding them instead shrinks
+    /// the alphabet.
+    pub fn disjoint_from(mut self, letters: &str) -> Self {
+        self.disjoint = letters.to_string();
+        self
+    }
+
+    /// Only find the solutions with this word (or an anagram of it)
+    /// among their words. The word starts every branch of the search
+    /// already placed, so only the rest of each set is searched for.
+    pub fn must_include(mut self, word: &str) -> Self {
+        self.include = Some(word.to_string());
+        self
+    }
+
+    /// Stop after this many solutions.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.config.limit = Some(limit);
+        self
+    }
+
+    /// Keep at most this many solutions for each (first) unused letter.
+    pub fn per_letter_limit(mut self, limit: usize) -> Self {
+        self.config.per_letter_limit = Some(limit);
+        self
+    }
+
+    /// The order [`Solver::solve`] returns solutions in.
+    pub fn order(mut self, order: Order) -> Self {
+        self.config.order = order;
+        self
+    }
+
+    /// The order the search starts its branches in (default
+    /// [`SeedOrder::Natural`]).
+    pub fn seed_order(mut self, order: SeedOrder) -> Self {
+        self.config.seed_order = order;
+        self
+    }
+
+    /// How the search is run (default [`Backend::Recursive`]).
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.config.backend = backend;
+        self
+    }
+
+    /// Which words count as distinct candidates (default
+    /// [`Dedup::Bitmask`]).
+    pub fn dedup_by(mut self, dedup: Dedup) -> Self {
+        self.config.dedup = dedup;
+        self
+    }
+
+    /// Which spelling of a set of letters solutions show, with
+    /// [`Dedup::Bitmask`] (default [`Anagram::First`]).
+    pub fn select_anagram(mut self, anagram: Anagram) -> Self {
+        self.config.anagram = anagram;
+        self
+    }
+
+    /// With [`Dedup::Original`], keep at most `max` spellings of each
+    /// set of letters (the first alphabetically), so a word with many
+    /// anagrams can only multiply its solutions so far.
+    pub fn max_spellings(mut self, max: usize) -> Self {
+        self.config.max_spellings = Some(max);
+        self
+    }
+
+    /// Limit the search trace debug builds print to branches less than
+    /// `depth` words deep (default: every depth).
+    pub fn max_trace_depth(mut self, depth: usize) -> Self {
+        self.config.trace_depth = Some(depth);
+        self
+    }
+
+    /// Only use words that also appear in this list.
+    pub fn intersect<S: Into<String>>(mut self, words: impl IntoIterator<Item = S>) -> Self {
+        self.config.intersect = Some(words.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Let a solution's words share letters, as long as they cover at
+    /// least `min` distinct letters between them (a disjoint set of
+    /// five five-letter words covers 25). Each word still has distinct
+    /// letters of its own, and each set is still found once. This
+    /// search runs on the one thread, as a single seed (`None`), and
+    /// every letter of overlap allowed makes it a great deal longer.
+    pub fn min_unique(mut self, min: usize) -> Self {
+        self.config.min_unique = Some(min);
+        self
+    }
+
+    /// Like [`min_unique`](Self::min_unique), but the words cover at
+    /// least `range.start()` and at most `range.end()` letters between
+    /// them, so `c..=c` finds the sets covering exactly `c`.
+    pub fn unique_range(mut self, range: RangeInclusive<usize>) -> Self {
+        self.config.min_unique = Some(*range.start());
+        self.config.max_unique = *range.end();
+        self
+    }
+
+    /// Leave out words whose spelling contains `bigram` (say "qu"),
+    /// whatever its case. It's checked on each spelling in the
+    /// dictionary, before anagrams are collapsed, so another spelling
+    /// of the same letters without it stands in for them. It may be
+    /// given more than once.
+    pub fn forbid_bigram(mut self, bigram: &str) -> Self {
+        self.config.forbidden.push(bigram.to_ascii_lowercase());
+        self
+    }
+
+    /// Order the search by how often each letter appears in `corpus`
+    /// (any text), rather than by their counts among the candidate
+    /// words. This can only change how quickly, and in what order,
+    /// solutions are found: the candidates still come from the
+    /// dictionary being solved.
+    pub fn letter_frequencies(mut self, corpus: &str) -> Self {
+        let mut freq = [0; MAX_SYMBOLS];
+
+        for z in corpus.chars().filter_map(|c| ZChar::try_from(c).ok()) {
+            freq[z.ord()] += 1;
+        }
+
+        self.config.letter_freq = Some(freq);
+        self
+    }
+
+    /// Only keep the solutions `filter` accepts, for rules of your own
+    /// (say, that some word starts with 's'). It sees each complete
+    /// solution before the limits count it, so the limits only count
+    /// the solutions it keeps.
+    pub fn filter(mut self, filter: impl Fn(&Solution) -> bool + Send + Sync + 'static) -> Self {
+        self.config.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Hand `log` each step the search takes (see [`Event`]), to trace
+    /// how it finds its solutions. The events of a branch arrive in
+    /// the order it takes them, but several threads' branches
+    /// interleave, so for one coherent trace search on a single
+    /// thread (`threads(1)`). Logging every step slows the search
+    /// down a great deal.
+    pub fn event_log(mut self, log: impl Fn(&Event) + Send + Sync + 'static) -> Self {
+        self.config.events = Some(Arc::new(log));
+        self
+    }
+
+    /// Tell `report` how far the search has got: once as it starts,
+    /// and again as each of its branches finishes (on whichever thread
+    /// searched it). Unlike an [`event_log`](Self::event_log), this
+    /// costs the search next to nothing.
+    pub fn on_progress(mut self, report: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        self.config.progress = Some(Arc::new(report));
+        self
+    }
+
+    /// Stop searching once `flag` is set (from another thread, or a
+    /// Ctrl-C handler). The solve then returns straight away with the
+    /// solutions found so far, and never calls `seed_done` for a seed
+    /// it didn't finish.
+    pub fn cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.config.cancel = Some(flag);
+        self
+    }
+
+    /// Search on exactly `threads` threads, dealing the seeds (the
+    /// letters the search's branches skip first, in the order of the
+    /// remapped bits) out to them in turn: the first seed to the first
+    /// thread, the second to the second, and round again after the
+    /// last. Which thread searches which branch then never depends on
+    /// scheduling, and neither do the limits: each seed keeps its own
+    /// solutions, which are only limited and passed on once every seed
+    /// is done, branch by branch as [`Order::Found`] lists them (so
+    /// with a limit, [`solve_with`](Solver::solve_with) hands them all
+    /// over at the end). Without it the branches are searched on
+    /// whichever of rayon's threads is free.
+    #[cfg(feature = "std")]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.config.threads = Some(threads);
+        self
+    }
+
+    /// Search on the threads of `pool`, rather than rayon's global
+    /// pool, or (with [`threads`](Self::threads)) threads spawned for
+    /// each solve, which then become tasks on the pool, dealt the seeds
+    /// just the same. Sharing one pool between solvers and solves saves
+    /// starting threads for every query when there are many small ones.
+    #[cfg(feature = "std")]
+    pub fn thread_pool(mut self, pool: Arc<rayon::ThreadPool>) -> Self {
+        self.config.pool = Some(pool);
+        self
+    }
+
+    /// Stop searching once a solve has run for `budget` (indexing the
+    /// dictionary included), and return the solutions found so far.
+    /// Like a [`cancel_flag`](Self::cancel_flag), it never calls
+    /// `seed_done` for a seed it didn't finish; [`Stats::timed_out`]
+    /// tells whether it ran out.
+    #[cfg(feature = "std")]
+    pub fn time_budget(mut self, budget: Duration) -> Self {
+        self.config.time_budget = Some(budget);
+        self
+    }
+
+    /// Only use words whose letters have at least one other spelling
+    /// in the dictionary (default false), so every word in a solution
+    /// has an alternative.
+    pub fn require_anagrammable(mut self, require: bool) -> Self {
+        self.config.require_anagrammable = require;
+        self
+    }
+
+    /// Only use words with at least one vowel (default false).
+    pub fn require_vowel(mut self, require: bool) -> Self {
+        self.config.require_vowel = require;
+        self
+    }
+
+    /// Whether 'y' counts as a vowel for
+    /// [`require_vowel`](Self::require_vowel) (default false).
+    pub fn y_vowel(mut self, y_vowel: bool) -> Self {
+        self.config.y_vowel = y_vowel;
+        self
+    }
+
+    /// How to normalize dictionary words before reading their letters.
+    #[cfg(feature = "unicode")]
+    pub fn normalize(mut self, normalize: Normalize) -> Self {
+        self.config.normalize = normalize;
+        self
+    }
+
+    pub fn build(self) -> Result<Solver, Error> {
+        let mut config = self.config;
+
+        if let Some(allowed) = &self.allowed {
+            config.allowed = letters_mask(allowed)?;
+        }
+        config.required = letters_mask(&self.required)?;
+        config.excluded = letters_mask(&self.excluded)?;
+        config.disjoint = letters_mask(&self.disjoint)?;
+
+        if config.word_len == 0 || config.set_size == 0 {
+            return Err(Error::ZeroSize);
+        }
+        if config.max_word_len < config.word_len {
+            return Err(Error::EmptyLengthRange);
+        }
+
+        #[cfg(feature = "std")]
+        if config.threads == Some(0) {
+            return Err(Error::ZeroThreads);
+        }
+
+        if config.max_spellings == Some(0) {
+            return Err(Error::ZeroSpellings);
+        }
+
+        let unavailable = config.required & !config.usable();
+
+        if unavailable != 0 {
+            let letter = ZChar(unavailable.trailing_zeros() as u8);
+
+            return Err(Error::RequiredUnavailable(letter.chr()));
+        }
+
+        if let Some(word) = &self.include {
+            let (letters, bits) = read_letters(word).map_err(Error::UnusableWord)?;
+
+            if !config.lengths().contains(&letters.len()) {
+                return Err(Error::UnusableWord(Problem::WrongLength(letters.len())));
+            }
+
+            let unavailable = bits & !config.usable();
+
+            if unavailable != 0 {
+                let letter = ZChar(unavailable.trailing_zeros() as u8);
+
+                return Err(Error::RequiredUnavailable(letter.chr()));
+            }
+
+            config.include = Some(bits);
+        }
+
+        if config.min_unique.is_some_and(|min| min > config.max_unique) {
+            return Err(Error::EmptyUniqueRange);
+        }
+
+        let needed = config
+            .min_unique
+            .unwrap_or(config.word_len * config.set_size);
+        let available = config.usable().count_ones() as usize;
+
+        if needed > available {
+            return Err(Error::TooFewLetters { needed, available });
+        }
+
+        let most = config.max_word_len * config.set_size;
+
+        if needed > most {
+            return Err(Error::CoverageOutOfReach { needed, most });
+        }
+
+        Ok(Solver { config })
+    }
+}
+
+fn letters_mask(letters: &str) -> Result<u64, Error> {
+    letters
+        .chars()
+        .try_fold(0, |mask, c| match ZChar::try_from(c) {
+            Ok(z) => Ok(mask | z.mask()),
+            Err(c) => Err(Error::InvalidLetter(c)),
+        })
+}
+
+/// Finds sets of letter-disjoint words, as configured by a
+/// [`SolverBuilder`].
+#[derive(Clone)]
+pub struct Solver {
+    config: Config,
+}
+
+impl Solver {
+    /// Reads `dict` (one word per line) into a [`PreparedDict`],
+    /// normalized the way this solver is configured to.
+    pub fn prepare<'a>(&self, dict: &'a str) -> PreparedDict<'a> {
+        #[cfg(feature = "unicode")]
+        return PreparedDict::normalized(dict, self.config.normalize);
+
+        #[cfg(not(feature = "unicode"))]
+        PreparedDict::new(dict)
+    }
+
+    /// Like [`prepare`](Self::prepare), reading the dictionary from
+    /// `reader` a line at a time rather than from its whole text.
+    #[cfg(feature = "std")]
+    pub fn prepare_reader(
+        &self,
+        reader: impl std::io::BufRead,
+    ) -> std::io::Result<PreparedDict<'static>> {
+        #[cfg(feature = "unicode")]
+        return PreparedDict::read(reader, |word| self.config.normalize.apply(word));
+
+        #[cfg(not(feature = "unicode"))]
+        PreparedDict::from_reader(reader)
+    }
+
+    /// Like [`prepare`](Self::prepare), from the dictionary's raw bytes
+    /// (see [`PreparedDict::from_bytes`]).
+    pub fn prepare_bytes<'a>(&self, dict: &'a [u8]) -> Result<PreparedDict<'a>, NotUtf8> {
+        #[cfg(feature = "unicode")]
+        return PreparedDict::read_bytes(dict, |word| self.config.normalize.apply(word));
+
+        #[cfg(not(feature = "unicode"))]
+        PreparedDict::from_bytes(dict)
+    }
+
+    /// Checks that every (non-blank) line of `dict` is a word the
+    /// solver can use: distinct plain letters, of the word length,
+    /// once normalized. Solving just skips any other lines; this finds
+    /// the first of them.
+    pub fn validate(&self, dict: &str) -> Result<(), MalformedWord> {
+        match self.malformed(dict).next() {
+            Some(malformed) => Err(malformed),
+            None => Ok(()),
+        }
+    }
+
+    /// Every line of `dict` that [`validate`](Self::validate) would
+    /// object to, in order.
+    pub fn malformed<'a>(&'a self, dict: &'a str) -> impl Iterator<Item = MalformedWord> + 'a {
+        dict.lines().enumerate().filter_map(|(i, line)| {
+            let line = line.trim();
+
+            #[cfg(feature = "unicode")]
+            let normalized = self.config.normalize.apply(line);
+            #[cfg(feature = "unicode")]
+            let line: &str = &normalized;
+
+            if line.is_empty() {
+                return None;
+            }
+
+            let problem = match read_letters(line) {
+                Ok((word, _)) if self.config.lengths().contains(&word.len()) => return None,
+                Ok((word, _)) => Problem::WrongLength(word.len()),
+                Err(problem) => problem,
+            };
+
+            Some(MalformedWord::new(
+                i + 1,
+                line,
+                problem,
+                self.config.lengths(),
+            ))
+        })
+    }
+
+    /// Reads `word` as the solver reads each dictionary line: trimmed,
+    /// normalized, and as distinct plain letters, but of any length
+    /// and regardless of the solver's constraints.
+    pub fn parse_word(&self, word: &str) -> Result<ZWord, Problem> {
+        let word = word.trim();
+
+        #[cfg(feature = "unicode")]
+        let normalized = self.config.normalize.apply(word);
+        #[cfg(feature = "unicode")]
+        let word: &str = &normalized;
+
+        read_letters(word).map(|(word, _)| word)
+    }
+
+    /// The candidate words in `dict`: those this solver's constraints
+    /// allow, one spelling for each set of letters, in dictionary
+    /// order.
+    pub fn candidates(&self, dict: &PreparedDict) -> Vec<ZWord> {
+        match self.config.wide() {
+            false => Index::<u32>::build(dict, &self.config).words,
+            true => Index::<u64>::build(dict, &self.config).words,
+        }
+    }
+
+    /// Builds the search's index of `dict` and checks its invariants:
+    /// that each candidate's mask has a bit for each of its letters,
+    /// that letters and bits map one-to-one, that the buckets hold
+    /// exactly the candidates, that every mask decodes back to its
+    /// word, and (when the alphabet fits the narrower masks) that
+    /// indexing with narrower and wider masks agrees.
+    pub fn self_test(&self, dict: &PreparedDict) -> Vec<Check> {
+        let wide = Index::<u64>::build(dict, &self.config);
+
+        if self.config.wide() {
+            return selftest::run(&wide, self.config.lengths());
+        }
+
+        let narrow = Index::<u32>::build(dict, &self.config);
+
+        let mut checks = selftest::run(&narrow, self.config.lengths());
+        checks.push(selftest::widths_agree(&narrow, &wide));
+        checks
+    }
+
+    /// How many candidates the search has to try for each letter: the
+    /// size of its bucket, the words whose rarest letter it is. There's
+    /// one entry per letter, in the order the search gives them their
+    /// bits (rarest first).
+    pub fn bucket_sizes(&self, dict: &PreparedDict) -> Vec<(ZChar, usize)> {
+        fn sizes<M: Mask>(index: Index<M>) -> Vec<(ZChar, usize)> {
+            (0..index.symbols)
+                .map(|bit| (index.bit_to_char[bit as usize], index.bucket(bit).len()))
+                .collect()
+        }
+
+        match self.config.wide() {
+            false => sizes(Index::<u32>::build(dict, &self.config)),
+            true => sizes(Index::<u64>::build(dict, &self.config)),
+        }
+    }
+
+    /// Which of the `rarest` rarest letters (by their counts among the
+    /// candidates, or the [`letter_frequencies`](SolverBuilder::letter_frequencies)
+    /// given) turn up together in any candidate, and how often. This
+    /// only indexes `dict`, without searching it.
+    pub fn letter_pairs(&self, dict: &PreparedDict, rarest: usize) -> LetterPairs {
+        match self.config.wide() {
+            false => pairs::count(&Index::<u32>::build(dict, &self.config), rarest),
+            true => pairs::count(&Index::<u64>::build(dict, &self.config), rarest),
+        }
+    }
+
+    /// The [`candidates`](Self::candidates) that aren't in any
+    /// solution, which a curated dictionary could do without. This is
+    /// a full solve of `dict` (ignoring any limits), so it takes as
+    /// long as one.
+    pub fn unsolvable(&self, dict: &PreparedDict) -> Vec<ZWord> {
+        let mut unlimited = self.clone();
+
+        unlimited.config.limit = None;
+        unlimited.config.per_letter_limit = None;
+
+        let bits = |word: &ZWord| word.iter().fold(0, |acc, z| acc | z.mask());
+        let used = Mutex::new(Set::<u64>::new());
+
+        unlimited.solve_prepared_with(dict, |solution| {
+            let mut used = used.lock().unwrap();
+
+            for word in solution.words() {
+                used.insert(bits(word));
+            }
+        });
+
+        let used = used.into_inner().unwrap();
+
+        self.candidates(dict)
+            .into_iter()
+            .filter(|word| !used.contains(&bits(word)))
+            .collect()
+    }
+
+    /// Why `word` is, or isn't, in this solver's solutions from `dict`.
+    /// Finding how many solutions a candidate is in means solving the
+    /// whole dictionary, so this takes as long as a solve.
+    pub fn explain(&self, dict: &PreparedDict, word: &str) -> Explanation {
+        #[cfg(feature = "unicode")]
+        let normalized = self.config.normalize.apply(word);
+        #[cfg(feature = "unicode")]
+        let word: &str = &normalized;
+
+        let (own, bits, shown) = match explain::check(&self.config, dict, word) {
+            Ok(candidate) => candidate,
+            Err(rejected) => return rejected,
+        };
+
+        // keeping every spelling, only solutions with this one count
+        //
+        let ours = |w: &ZWord| match self.config.dedup {
+            Dedup::Bitmask => w.iter().fold(0, |acc, z| acc | z.mask()) == bits,
+            Dedup::Original => w.iter().eq(own.iter()),
+        };
+
+        let count = AtomicUsize::new(0);
+
+        self.solve_prepared_with(dict, |solution| {
+            if solution.words().iter().any(ours) {
+                count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        match count.into_inner() {
+            0 => Explanation::NoSolutions,
+            count => {
+                let shown = shown.to_string();
+                let anagram =
+                    self.config.dedup == Dedup::Bitmask && !shown.eq_ignore_ascii_case(word.trim());
+
+                Explanation::Solutions {
+                    count,
+                    anagram: anagram.then_some(shown),
+                }
+            }
+        }
+    }
+
+    /// Every solution in `dict` (one word per line). Each set of
+    /// words comes once, never again in another order: the search
+    /// places a set's words in the order of their rarest letters, so
+    /// there's only the one way down to it.
+    pub fn solve(&self, dict: &str) -> Vec<Solution> {
+        self.solve_prepared(&self.prepare(dict))
+    }
+
+    /// Every solution in an already prepared dictionary. The solver's
+    /// normalization doesn't apply here, as the dictionary was
+    /// normalized when it was prepared.
+    pub fn solve_prepared(&self, dict: &PreparedDict) -> Vec<Solution> {
+        self.solve_prepared_stats(dict).0
+    }
+
+    /// Like [`solve_prepared`](Self::solve_prepared), also returning
+    /// the counts and timings of the solve.
+    pub fn solve_prepared_stats(&self, dict: &PreparedDict) -> (Vec<Solution>, Stats) {
+        // each seed's branch is searched by a single thread, which keeps
+        // the branch's solutions to itself, so the threads never wait on
+        // each other; the lists are only joined up at the end (in seed
+        // order, so the result doesn't depend on thread timing)
+        //
+        let (stats, mut solutions) = match self.config.wide() {
+            false => self.run_with::<u32>(dict, |_| true, |_, _| {}, |_| {}, true),
+            true => self.run_with::<u64>(dict, |_| true, |_, _| {}, |_| {}, true),
+        };
+
+        match self.config.order {
+            Order::Found => {}
+            Order::Alphabetical => solutions.sort_by_cached_key(Solution::to_string),
+            Order::Unused => solutions.sort_by_cached_key(|solution| {
+                let unused: String = solution.unused.iter().map(ZChar::chr).collect();
+
+                (unused, solution.to_string())
+            }),
+        }
+
+        (solutions, stats)
+    }
+
+    /// Hands each solution in `dict` to `emit` as soon as it is found
+    /// (from any of the search threads, so in no particular order).
+    pub fn solve_with(&self, dict: &str, emit: impl Fn(Solution) + Sync) -> Stats {
+        self.solve_seeds(dict, |_| true, |_, solution| emit(solution), |_| {})
+    }
+
+    /// Like [`solve_with`](Self::solve_with), over an already prepared
+    /// dictionary.
+    pub fn solve_prepared_with(
+        &self,
+        dict: &PreparedDict,
+        emit: impl Fn(Solution) + Sync,
+    ) -> Stats {
+        self.run(dict, |_| true, |_, solution| emit(solution), |_| {})
+    }
+
+    /// Like [`solve_with`](Self::solve_with), but only searches the
+    /// branches (seeds) `run_seed` accepts, tells `emit` which seed
+    /// found each solution, and calls `seed_done` as each seed's branch
+    /// is exhausted. Seeds partition the solutions, so a run can be
+    /// split up (or resumed) seed by seed.
+    pub fn solve_seeds(
+        &self,
+        dict: &str,
+        run_seed: impl Fn(Seed) -> bool + Sync,
+        emit: impl Fn(Seed, Solution) + Sync,
+        seed_done: impl Fn(Seed) + Sync,
+    ) -> Stats {
+        let prepare_timer = Instant::now();
+        let dict = self.prepare(dict);
+        let prepare_time = prepare_timer.elapsed();
+
+        let mut stats = self.run(&dict, run_seed, emit, seed_done);
+
+        stats.index_time += prepare_time;
+        stats
+    }
+
+    /// Like [`solve_seeds`](Self::solve_seeds), over an already
+    /// prepared dictionary.
+    pub fn solve_prepared_seeds(
+        &self,
+        dict: &PreparedDict,
+        run_seed: impl Fn(Seed) -> bool + Sync,
+        emit: impl Fn(Seed, Solution) + Sync,
+        seed_done: impl Fn(Seed) + Sync,
+    ) -> Stats {
+        self.run(dict, run_seed, emit, seed_done)
+    }
+
+    fn run(
+        &self,
+        dict: &PreparedDict,
+        run_seed: impl Fn(Seed) -> bool + Sync,
+        emit: impl Fn(Seed, Solution) + Sync,
+        seed_done: impl Fn(Seed) + Sync,
+    ) -> Stats {
+        // (the alphabet picks the width once, so the search itself is
+        // the same code either way, just monomorphized)
+        //
+        match self.config.wide() {
+            false => {
+                self.run_with::<u32>(dict, run_seed, emit, seed_done, false)
+                    .0
+            }
+            true => {
+                self.run_with::<u64>(dict, run_seed, emit, seed_done, false)
+                    .0
+            }
+        }
+    }
+
+    // the search itself, over masks of width `M`. When `collect`, the
+    // solutions aren't handed to `emit` but kept by the branch that
+    // found them, and returned once the search is done, branch by
+    // branch in the seeds' alphabetical order
+    //
+    fn run_with<M: Mask>(
+        &self,
+        dict: &PreparedDict,
+        run_seed: impl Fn(Seed) -> bool + Sync,
+        emit: impl Fn(Seed, Solution) + Sync,
+        seed_done: impl Fn(Seed) + Sync,
+        collect: bool,
+    ) -> (Stats, Vec<Solution>) {
+        let config = &self.config;
+
+        #[cfg(feature = "std")]
+        let deadline = config.time_budget.map(|budget| Instant::now() + budget);
+
+        let index_timer = Instant::now();
+        let index = Index::<M>::build(dict, config);
+        let index_time = index_timer.elapsed();
+
+        let search_timer = Instant::now();
+
+        let required = index.remap(config.required);
+        let stop = AtomicBool::new(false);
+        let expired = AtomicBool::new(false);
+        let letter_counts: Vec<AtomicUsize> =
+            (0..MAX_SYMBOLS).map(|_| Default::default()).collect();
+        let solution_count = AtomicUsize::new(0);
+        let branches_done = AtomicUsize::new(0);
+        // (a lock rather than an atomic, as not every target has 64-bit
+        // atomics; it's only taken once per seed)
+        //
+        let visits = Mutex::new(0);
+
+        let spare = config.spare_letters();
+
+        // with words of several lengths, a solution may still cover
+        // every usable letter, which only a branch skipping none finds:
+        // it's searched alongside the seeds, as the seed past the last
+        // letter (`WHOLE`)
+        //
+        let whole = spare > 0
+            && config.max_word_len * config.set_size >= config.usable().count_ones() as usize;
+
+        #[cfg(feature = "std")]
+        let threads = config
+            .threads
+            .filter(|_| spare > 0 && config.allow_skip && config.min_unique.is_none());
+        #[cfg(not(feature = "std"))]
+        let threads: Option<usize> = None;
+
+        // on a fixed partition, a limited search holds on to each seed's
+        // solutions (up to the limit), to limit them in seed order once
+        // they're all in; `full` stops a seed that has enough, and
+        // `finished` marks the seeds whose branch ran to the end
+        //
+        let hold =
+            threads.is_some() && (config.limit.is_some() || config.per_letter_limit.is_some());
+        let held: Vec<Mutex<Vec<Solution>>> = match hold {
+            true => (0..=MAX_SYMBOLS).map(|_| Default::default()).collect(),
+            false => Vec::new(),
+        };
+        let full: Vec<AtomicBool> = (0..=MAX_SYMBOLS).map(|_| Default::default()).collect();
+        #[cfg(feature = "std")]
+        let finished: Vec<AtomicBool> = (0..=MAX_SYMBOLS).map(|_| Default::default()).collect();
+
+        let seed_of = |seed: u32| match spare {
+            0 => None,
+            _ => index.bit_to_char.get(seed as usize).copied(),
+        };
+
+        // the word every solution includes, if it's a candidate at all
+        // (when it isn't, there are no solutions to search for)
+        //
+        let include = config.include.map(|bits| index.remap(bits));
+        let absent = include.is_some_and(|bits| !index.word_lut.contains_key(&bits));
+
+        let accept = |seed: u32, selected: &[M], found: &mut Vec<Solution>| {
+            let used = selected.iter().fold(M::ZERO, |acc, &bits| acc | bits);
+
+            if required & !used != M::ZERO {
+                return;
+            }
+
+            // (a disjoint search places it first, so it's put back in
+            // the place it would have been found in; the coverage search
+            // isn't given it, and only keeps the sets it turns up in)
+            //
+            let mut ordered;
+            let selected = match include {
+                Some(_) if config.min_unique.is_none() => {
+                    ordered = selected.to_vec();
+                    ordered.sort_by_key(|bits| bits.trailing_zeros());
+                    &ordered[..]
+                }
+                Some(bits) if !selected.contains(&bits) => return,
+                _ => selected,
+            };
+
+            let solutions = match config.dedup {
+                Dedup::Bitmask => vec![index.solution(selected)],
+                Dedup::Original => index.spelled(selected),
+            };
+
+            for solution in solutions {
+                if let Some(filter) = &config.filter {
+                    if !filter(&solution) {
+                        continue;
+                    }
+                }
+
+                if hold {
+                    let mut list = held[seed as usize].lock().unwrap();
+
+                    // (the per-letter limit may yet drop some of them,
+                    // so then the seed never has enough)
+                    //
+                    list.push(solution);
+                    if config.per_letter_limit.is_none()
+                        && config.limit.is_some_and(|limit| list.len() >= limit)
+                    {
+                        full[seed as usize].store(true, Ordering::Relaxed);
+                        return;
+                    }
+                    continue;
+                }
+
+                // with several unused letters, a solution is counted
+                // against the first of them (and with none, it is never
+                // limited)
+                //
+                if let (Some(limit), Some(first)) =
+                    (config.per_letter_limit, solution.unused.first())
+                {
+                    if letter_counts[first.ord()].fetch_add(1, Ordering::Relaxed) >= limit {
+                        continue;
+                    }
+                }
+
+                let n = solution_count.fetch_add(1, Ordering::Relaxed);
+
+                if let Some(limit) = config.limit {
+                    if n >= limit {
+                        return;
+                    }
+                    if n + 1 == limit {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                }
+
+                match collect {
+                    true => found.push(solution),
+                    false => emit(seed_of(seed), solution),
+                }
+            }
+        };
+
+        let ctx = Search {
+            index: &index,
+            required,
+            stop: &stop,
+            cancel: config.cancel.as_deref().unwrap_or(&stop),
+            #[cfg(feature = "std")]
+            deadline,
+            expired: &expired,
+            emit: &accept,
+            events: config.events.as_deref().map(|log| log as _),
+            trace_depth: config.trace_depth.unwrap_or(usize::MAX),
+        };
+
+        let search = match config.backend {
+            Backend::Recursive => search::<M>,
+            Backend::Iterative => search_iter::<M>,
+        };
+
+        // tell the caller, as each of the search's `branches` starts or
+        // is `finished`
+        //
+        let report = |branches: usize, finished: bool| {
+            if let Some(progress) = &config.progress {
+                let done = match finished {
+                    true => branches_done.fetch_add(1, Ordering::Relaxed) + 1,
+                    false => branches_done.load(Ordering::Relaxed),
+                };
+                let solutions = solution_count.load(Ordering::Relaxed);
+
+                progress(Progress {
+                    done,
+                    branches,
+                    solutions: config.limit.map_or(solutions, |limit| solutions.min(limit)),
+                });
+            }
+        };
+
+        // letters we may not use (and the bits past the alphabet's) are
+        // pre-set in every branch's mask, so the search treats them as
+        // already covered
+        //
+        let usable = index.remap(config.usable());
+        let outside = !usable;
+
+        // as is the word every solution includes: it's the first in
+        // every branch's set, leaving the search the rest to place
+        //
+        let (preset, start) = match include {
+            Some(bits) => (bits, 1),
+            None => (M::ZERO, 0),
+        };
+        let slots = || {
+            let mut selected = vec![M::ZERO; config.set_size];
+
+            if start > 0 {
+                selected[0] = preset;
+            }
+            selected
+        };
+
+        // each seed pre-sets the first (lowest) letter this branch skips,
+        // and the search may then skip further letters above it until it
+        // runs out of spare letters. (a seed with no skipped letter would
+        // just rediscover the solutions of the seed that skips the top
+        // bit, as we always fill the lowest free bit first)
+        //
+        // When the words cover the whole alphabet there is nothing to skip
+        // and we only have the one, empty, seed.
+        //
+        // (each branch's own solutions, when collecting them)
+        //
+        let mut lists: Vec<(u32, Vec<Solution>)> = Vec::new();
+
+        if absent {
+            // nothing to search
+        } else if let Some(min) = config.min_unique {
+            if run_seed(None) {
+                let mut selected = vec![M::ZERO; config.set_size];
+
+                ctx.log(|| Event::Seed { seed: None });
+                report(1, false);
+
+                let mut tally = Tally::default();
+                let skips = match config.allow_skip {
+                    true => usable.count_ones() as usize - min,
+                    false => 0,
+                };
+
+                Cover::new(
+                    &ctx,
+                    (min, config.max_unique),
+                    (config.word_len, config.max_word_len),
+                    config.set_size,
+                )
+                .search(
+                    &ctx,
+                    &mut selected,
+                    &mut tally,
+                    M::ZERO,
+                    outside,
+                    M::ZERO,
+                    &mut [0; MAX_SYMBOLS],
+                    0,
+                    skips,
+                    false,
+                );
+                *visits.lock().unwrap() += tally.visits;
+                lists.push((WHOLE, tally.found));
+                report(1, true);
+
+                if !ctx.stopped() {
+                    seed_done(None);
+                }
+            }
+        } else if spare == 0 {
+            if run_seed(None) {
+                let mut selected = slots();
+
+                ctx.log(|| Event::Seed { seed: None });
+                report(1, false);
+
+                let mut tally = Tally::default();
+
+                search(
+                    &ctx,
+                    &mut selected,
+                    &mut tally,
+                    outside | preset,
+                    start,
+                    0,
+                    0,
+                );
+                *visits.lock().unwrap() += tally.visits;
+                lists.push((WHOLE, tally.found));
+                report(1, true);
+
+                if !ctx.stopped() {
+                    seed_done(None);
+                }
+            }
+        } else if config.allow_skip {
+            let seeds = usable & !required & !preset;
+            let searched = |i: u32| {
+                let seeded = match i {
+                    WHOLE => whole,
+                    _ => seeds & M::bit(i) != M::ZERO,
+                };
+                seeded && run_seed(seed_of(i))
+            };
+
+            // each branch's size, as the candidates it may use (those
+            // without the letter it skips)
+            //
+            let size = |i: u32| match i {
+                WHOLE => index.lbit_lut.len(),
+                _ => index
+                    .lbit_lut
+                    .iter()
+                    .filter(|&&word| word & M::bit(i) == M::ZERO)
+                    .count(),
+            };
+
+            let mut order: Vec<u32> = (0..index.symbols)
+                .chain([WHOLE])
+                .filter(|&i| searched(i))
+                .collect();
+
+            match config.seed_order {
+                SeedOrder::Natural => {}
+                SeedOrder::SizeDesc => order.sort_by_cached_key(|&i| core::cmp::Reverse(size(i))),
+                SeedOrder::SizeAsc => order.sort_by_cached_key(|&i| size(i)),
+            }
+
+            let total = order.len();
+            report(total, false);
+
+            let branch = |ctx: &Search<M>, i: u32| {
+                let mut selected = slots();
+
+                ctx.log(|| Event::Seed { seed: seed_of(i) });
+
+                let mut tally = Tally::default();
+
+                // (each thread counts its own visits, and only adds them
+                // up once the branch is done)
+                //
+                let (mask, skips) = match i {
+                    WHOLE => (outside | preset, 0),
+                    _ => (outside | preset | M::bit(i), spare - 1),
+                };
+
+                search(ctx, &mut selected, &mut tally, mask, start, skips, i);
+                *visits.lock().unwrap() += tally.visits;
+                report(total, true);
+                tally.found
+            };
+
+            match threads {
+                #[cfg(feature = "std")]
+                Some(threads) => {
+                    let dealt = order;
+
+                    // the `first` thread's share of the seeds, and what
+                    // each of them found
+                    //
+                    let deal = |first: usize| {
+                        let mut found = Vec::new();
+
+                        for &i in dealt.iter().skip(first).step_by(threads) {
+                            match hold {
+                                true => {
+                                    let ctx = Search {
+                                        stop: &full[i as usize],
+                                        ..ctx
+                                    };
+
+                                    branch(&ctx, i);
+                                    finished[i as usize].store(!ctx.stopped(), Ordering::Relaxed);
+                                }
+                                false => {
+                                    found.push((i, branch(&ctx, i)));
+                                    if !ctx.stopped() {
+                                        seed_done(seed_of(i));
+                                    }
+                                }
+                            }
+                        }
+                        found
+                    };
+
+                    let found: Vec<Vec<_>> = match &config.pool {
+                        Some(pool) => {
+                            pool.install(|| (0..threads).into_par_iter().map(deal).collect())
+                        }
+                        None => std::thread::scope(|scope| {
+                            let dealers: Vec<_> = (0..threads)
+                                .map(|first| {
+                                    let deal = &deal;
+                                    scope.spawn(move || deal(first))
+                                })
+                                .collect();
+
+                            dealers
+                                .into_iter()
+                                .map(|dealer| dealer.join().unwrap())
+                                .collect()
+                        }),
+                    };
+
+                    lists.extend(found.into_iter().flatten());
+
+                    // (in the seeds' alphabetical order, as
+                    // solve_prepared lists them)
+                    //
+                    if hold {
+                        let mut letter_counts = [0; MAX_SYMBOLS];
+                        let mut dealt = dealt;
+
+                        dealt.sort_by_key(|&i| seed_of(i).map_or(MAX_SYMBOLS, |z| z.ord()));
+
+                        for &i in &dealt {
+                            let list = core::mem::take(&mut *held[i as usize].lock().unwrap());
+                            let mut done = finished[i as usize].load(Ordering::Relaxed);
+                            let mut kept = Vec::new();
+
+                            for solution in list {
+                                if let (Some(limit), Some(first)) =
+                                    (config.per_letter_limit, solution.unused.first())
+                                {
+                                    letter_counts[first.ord()] += 1;
+                                    if letter_counts[first.ord()] > limit {
+                                        continue;
+                                    }
+                                }
+
+                                let n = solution_count.fetch_add(1, Ordering::Relaxed);
+
+                                if config.limit.is_some_and(|limit| n >= limit) {
+                                    done = false;
+                                    break;
+                                }
+
+                                match collect {
+                                    true => kept.push(solution),
+                                    false => emit(seed_of(i), solution),
+                                }
+                            }
+
+                            lists.push((i, kept));
+                            if done {
+                                seed_done(seed_of(i));
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    // (the seeds' branches are independent, so with std
+                    // each is searched on whichever thread is free)
+                    //
+                    #[cfg(feature = "std")]
+                    let branches = order.into_par_iter();
+                    #[cfg(not(feature = "std"))]
+                    let branches = order.into_iter();
+
+                    // (collected in the branches' order, whichever
+                    // thread searched each)
+                    //
+                    let search_all = || -> Vec<_> {
+                        branches
+                            .map(|i| {
+                                let found = branch(&ctx, i);
+
+                                // a branch cut short (by the limit, or
+                                // the caller) isn't exhausted
+                                //
+                                if !ctx.stopped() {
+                                    seed_done(seed_of(i));
+                                }
+                                (i, found)
+                            })
+                            .collect()
+                    };
+
+                    #[cfg(feature = "std")]
+                    let found = match &config.pool {
+                        Some(pool) => pool.install(search_all),
+                        None => search_all(),
+                    };
+                    #[cfg(not(feature = "std"))]
+                    let found = search_all();
+
+                    lists.extend(found);
+                }
+            }
+        } else if whole {
+            // with no letters to skip, only the branch covering every
+            // letter can finish a set
+            //
+            if run_seed(None) {
+                let mut selected = slots();
+
+                ctx.log(|| Event::Seed { seed: None });
+                report(1, false);
+
+                let mut tally = Tally::default();
+
+                search(
+                    &ctx,
+                    &mut selected,
+                    &mut tally,
+                    outside | preset,
+                    start,
+                    0,
+                    WHOLE,
+                );
+                *visits.lock().unwrap() += tally.visits;
+                lists.push((WHOLE, tally.found));
+                report(1, true);
+
+                if !ctx.stopped() {
+                    seed_done(None);
+                }
+            }
+        }
+
+        // (in the seeds' alphabetical order, as solve_prepared lists
+        // them)
+        //
+        lists.sort_by_key(|&(i, _)| seed_of(i).map_or(MAX_SYMBOLS, |z| z.ord()));
+
+        let found = lists.into_iter().flat_map(|(_, found)| found).collect();
+        let solutions = solution_count.into_inner();
+
+        let stats = Stats {
+            candidates: index.candidates(),
+            solutions: config.limit.map_or(solutions, |limit| solutions.min(limit)),
+            visits: visits.into_inner().unwrap(),
+            cancelled: config
+                .cancel
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::Relaxed)),
+            timed_out: expired.into_inner(),
+            index_time,
+            search_time: search_timer.elapsed(),
+        };
+
+        (stats, found)
+    }
+}
@@ -0,0 +1,76 @@
+use rusqlite::{params_from_iter, Connection};
+
+// --sqlite: the solutions as rows of a "solutions" table, one column
+// per word (in the order the search picked them) and one for the
+// unused letters:
+//
+//   CREATE TABLE solutions (
+//       id INTEGER PRIMARY KEY,
+//       word1 TEXT NOT NULL, ..., wordN TEXT NOT NULL,
+//       unused TEXT NOT NULL
+//   )
+//
+// An existing table of that name is replaced, and the rest of the
+// database left alone. Every row goes in the one transaction, which
+// only commits once the run is done.
+//
+pub struct Writer {
+    conn: Connection,
+    insert: String,
+    rows: usize,
+}
+
+impl Writer {
+    pub fn create(path: &str, set_size: usize) -> Result<Self, String> {
+        let conn = Connection::open(path).map_err(|err| err.to_string())?;
+
+        let words: Vec<String> = (1..=set_size).map(|i| format!("word{}", i)).collect();
+        let columns: Vec<String> = words
+            .iter()
+            .map(|word| format!("{} TEXT NOT NULL", word))
+            .collect();
+
+        conn.execute_batch(&format!(
+            "DROP TABLE IF EXISTS solutions;
+             CREATE TABLE solutions (id INTEGER PRIMARY KEY, {}, unused TEXT NOT NULL);
+             BEGIN;",
+            columns.join(", ")
+        ))
+        .map_err(|err| err.to_string())?;
+
+        Ok(Writer {
+            conn,
+            insert: format!(
+                "INSERT INTO solutions ({}, unused) VALUES ({})",
+                words.join(", "),
+                vec!["?"; set_size + 1].join(", ")
+            ),
+            rows: 0,
+        })
+    }
+
+    // a solution's row, with its words and unused letters as they're to
+    // be spelled
+    //
+    pub fn write(&mut self, words: &[String], unused: &str) -> Result<(), String> {
+        let words = words.iter().map(String::as_str);
+
+        self.conn
+            .prepare_cached(&self.insert)
+            .and_then(|mut insert| insert.execute(params_from_iter(words.chain([unused]))))
+            .map_err(|err| err.to_string())?;
+
+        self.rows += 1;
+        Ok(())
+    }
+
+    // commit the rows, returning how many there are
+    //
+    pub fn finish(self) -> Result<usize, String> {
+        self.conn
+            .execute_batch("COMMIT")
+            .map_err(|err| err.to_string())?;
+
+        Ok(self.rows)
+    }
+}
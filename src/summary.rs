@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use fivewords::{PreparedDict, Solution, Stats, ZChar, MAX_SYMBOLS};
+
+// how many of the rarest letters the report lists
+//
+const RARE_LETTERS: usize = 5;
+
+// the end-of-run report for --summary: the solve's counts and timings,
+// which letters the solutions leave unused, and how often they manage
+// to use the dictionary's rarest letters. Solutions are tallied as
+// they're found, from any of the search threads.
+//
+pub struct Summary {
+    unused: [AtomicUsize; MAX_SYMBOLS],
+    used: [AtomicUsize; MAX_SYMBOLS],
+}
+
+impl Default for Summary {
+    fn default() -> Self {
+        Summary {
+            unused: [const { AtomicUsize::new(0) }; MAX_SYMBOLS],
+            used: [const { AtomicUsize::new(0) }; MAX_SYMBOLS],
+        }
+    }
+}
+
+impl Summary {
+    pub fn add(&self, solution: &Solution) {
+        for z in solution.unused() {
+            self.unused[z.ord()].fetch_add(1, Ordering::Relaxed);
+        }
+        for word in solution.words() {
+            for z in word {
+                self.used[z.ord()].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn print(
+        &self,
+        path: &str,
+        dict: &PreparedDict,
+        lengths: RangeInclusive<usize>,
+        stats: &Stats,
+    ) {
+        let unused: Vec<usize> = self
+            .unused
+            .iter()
+            .map(|n| n.load(Ordering::Relaxed))
+            .collect();
+        let used: Vec<usize> = self
+            .used
+            .iter()
+            .map(|n| n.load(Ordering::Relaxed))
+            .collect();
+
+        println!("== summary ==");
+        println!("  dictionary:  {}", path);
+        println!("  candidates:  {}", stats.candidates);
+        println!("  solutions:   {}", stats.solutions);
+        println!("  visits:      {}", stats.visits);
+        println!("  index ms:    {}", stats.index_time.as_millis());
+        println!("  search ms:   {}", stats.search_time.as_millis());
+
+        // unused letters, most common first, with a bar relative to the
+        // most common
+        //
+        let mut letters: Vec<usize> = (0..MAX_SYMBOLS).filter(|&i| unused[i] > 0).collect();
+        letters.sort_by_key(|&i| std::cmp::Reverse(unused[i]));
+
+        let widest = letters.first().map_or(1, |&i| unused[i]);
+
+        println!();
+        println!("  unused letter  solutions");
+
+        if letters.is_empty() {
+            println!("  (none)");
+        }
+        for i in letters {
+            let bar = (unused[i] * 30).div_ceil(widest);
+
+            println!("  {:>13}  {:>9}  {}", letter(i), unused[i], "#".repeat(bar));
+        }
+
+        // the letters fewest candidate words use, which are the hardest
+        // for a solution to cover
+        //
+        let mut freq = [0usize; MAX_SYMBOLS];
+
+        for word in dict.words().filter(|word| lengths.contains(&word.len())) {
+            for z in word {
+                freq[z.ord()] += 1;
+            }
+        }
+
+        let mut rare: Vec<usize> = (0..MAX_SYMBOLS).filter(|&i| freq[i] > 0).collect();
+        rare.sort_by_key(|&i| freq[i]);
+
+        println!();
+        println!("  rare letter  words  solutions using it");
+
+        for i in rare.into_iter().take(RARE_LETTERS) {
+            println!(
+                "  {:>11}  {:>5}  {} of {}",
+                letter(i),
+                freq[i],
+                used[i],
+                stats.solutions
+            );
+        }
+    }
+}
+
+// every letter (of those any solution could use) by how often it's
+// the one left unused, for --letters-used-report. With more than one
+// letter to spare, a solution counts towards each of its unused
+// letters, so the shares can add up to more than 100%.
+//
+pub fn print_letters_report(summary: &Summary, solutions: usize) {
+    let count = |counts: &[AtomicUsize; MAX_SYMBOLS], i: usize| counts[i].load(Ordering::Relaxed);
+
+    // a letter is in play if some solution either uses or skips it
+    //
+    let mut letters: Vec<usize> = (0..MAX_SYMBOLS)
+        .filter(|&i| count(&summary.used, i) + count(&summary.unused, i) > 0)
+        .collect();
+    letters.sort_by_key(|&i| std::cmp::Reverse(count(&summary.unused, i)));
+
+    println!("== unused letters ==");
+    println!("  letter  solutions   share");
+
+    for &i in &letters {
+        let unused = count(&summary.unused, i);
+
+        println!(
+            "  {:>6}  {:>9}  {:>5.1}%",
+            letter(i),
+            unused,
+            100.0 * unused as f64 / solutions.max(1) as f64
+        );
+    }
+
+    let never: String = letters
+        .iter()
+        .filter(|&&i| count(&summary.unused, i) == 0)
+        .map(|&i| letter(i))
+        .collect();
+
+    println!();
+    match never.is_empty() {
+        true => println!("  every letter is unused in some solution"),
+        false => println!("  never unused: {}", never),
+    }
+}
+
+// how many solutions each word is in, for --histogram-solutions: the
+// words the most solutions lean on come first
+//
+#[derive(Default)]
+pub struct WordHistogram {
+    counts: Mutex<HashMap<String, usize>>,
+}
+
+impl WordHistogram {
+    pub fn add(&self, solution: &Solution) {
+        let mut counts = self.counts.lock().unwrap();
+
+        for word in solution.words() {
+            *counts.entry(word.to_string()).or_default() += 1;
+        }
+    }
+
+    pub fn print(&self, word_len: usize) {
+        let mut words: Vec<_> = self.counts.lock().unwrap().drain().collect();
+        words.sort_by(|(a, m), (b, n)| n.cmp(m).then_with(|| a.cmp(b)));
+
+        let width = word_len.max("word".len());
+
+        println!("== words by solutions ==");
+        println!("  {:<width$}  solutions", "word");
+
+        if words.is_empty() {
+            println!("  (none)");
+        }
+        for (word, count) in words {
+            println!("  {:<width$}  {:>9}", word, count);
+        }
+    }
+}
+
+// how many solutions have each mix of word lengths, for
+// --count-by-length: the lengths of a solution's words, longest first,
+// make its shape ("6+5+5+5+4"). Only a --len-range run has more than
+// the one shape.
+//
+#[derive(Default)]
+pub struct LengthCounts {
+    counts: Mutex<BTreeMap<Vec<usize>, usize>>,
+}
+
+impl LengthCounts {
+    pub fn add(&self, solution: &Solution) {
+        let mut lengths: Vec<_> = solution.words().iter().map(|word| word.len()).collect();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+
+        *self.counts.lock().unwrap().entry(lengths).or_default() += 1;
+    }
+
+    pub fn print(&self) {
+        let counts = self.counts.lock().unwrap();
+
+        let shapes: Vec<_> = counts
+            .iter()
+            .map(|(lengths, &count)| {
+                let shape: Vec<_> = lengths.iter().map(usize::to_string).collect();
+                (shape.join("+"), count)
+            })
+            .collect();
+
+        let width = shapes
+            .iter()
+            .map(|(shape, _)| shape.len())
+            .max()
+            .unwrap_or(0)
+            .max("lengths".len());
+
+        println!("== solutions by word lengths ==");
+        println!("  {:<width$}  solutions", "lengths");
+
+        if shapes.is_empty() {
+            println!("  (none)");
+        }
+        for (shape, count) in shapes {
+            println!("  {:<width$}  {:>9}", shape, count);
+        }
+    }
+}
+
+fn letter(i: usize) -> char {
+    ZChar::from_ord(i).unwrap().chr()
+}
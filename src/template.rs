@@ -0,0 +1,142 @@
+use std::fmt::{self, Display, Formatter};
+
+// a user-supplied output line, such as "{w1}/{w2}/{w3} [{unused}]",
+// parsed once up front so rendering a solution is just a walk over
+// its pieces. The placeholders are:
+//
+//   {w1}, {w2}, ...  the words, in the order the search picked them
+//   {words}          all the words, separated by spaces
+//   {unused}         the unused letters, or "none"
+//
+// and "{{" and "}}" stand for literal braces.
+//
+#[derive(Clone)]
+pub struct Template {
+    source: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Clone)]
+enum Part {
+    Text(String),
+    Word(usize),
+    Words,
+    Unused,
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest
+                        .find('}')
+                        .ok_or_else(|| format!("unclosed '{{' in template '{}'", template))?;
+                    let name = &rest[..end];
+
+                    let part = match name {
+                        "words" => Part::Words,
+                        "unused" => Part::Unused,
+                        _ => match name.strip_prefix('w').and_then(|n| n.parse().ok()) {
+                            Some(n) if n > 0 => Part::Word(n),
+                            _ => {
+                                return Err(format!(
+                                    "unknown placeholder '{{{}}}' in template",
+                                    name
+                                ))
+                            }
+                        },
+                    };
+
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(part);
+
+                    chars = rest[end + 1..].chars();
+                }
+                '}' => return Err(format!("unmatched '}}' in template '{}'", template)),
+                _ => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Template {
+            source: template.to_string(),
+            parts,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    // the highest {wN} the template uses, so it can be checked against
+    // the number of words in a set
+    //
+    pub fn max_word(&self) -> usize {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Word(n) => *n,
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn render<'a, W: Display, U: Display>(
+        &'a self,
+        words: &'a [W],
+        unused: U,
+    ) -> Rendered<'a, W, U> {
+        Rendered {
+            template: self,
+            words,
+            unused,
+        }
+    }
+}
+
+pub struct Rendered<'a, W, U> {
+    template: &'a Template,
+    words: &'a [W],
+    unused: U,
+}
+
+impl<W: Display, U: Display> Display for Rendered<'_, W, U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for part in &self.template.parts {
+            match part {
+                Part::Text(text) => write!(f, "{}", text)?,
+                Part::Word(n) => write!(f, "{}", self.words[n - 1])?,
+                Part::Words => {
+                    for (i, word) in self.words.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, " ")?;
+                        }
+                        write!(f, "{}", word)?;
+                    }
+                }
+                Part::Unused => write!(f, "{}", self.unused)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+
+use fivewords::{ZChar, ZWord, MAX_SYMBOLS};
+
+// --tokens: a puzzle over units of several characters (syllables, say)
+// rather than single letters. The definition lists the tokens, one per
+// line (bar blank lines and '#' comments), at most 64 of them:
+//
+//   # kana
+//   ka
+//   ki
+//   ku
+//   ...
+//
+// Each token stands in for a letter ('a' for the first, 'b' for the
+// second, and so on, carrying on past 'z' with the library's further
+// symbols), so a dictionary word split into tokens becomes a word of
+// those letters, and the search runs over them unchanged; the
+// solutions are then spelled back in tokens. Words that can't be split
+// into tokens are skipped, like words with anything but letters.
+//
+#[derive(Default, Clone)]
+pub struct Tokens {
+    tokens: Vec<String>,
+
+    // the indices of the tokens, longest first, so a word is split
+    // into as few tokens as it can be
+    //
+    by_length: Vec<usize>,
+}
+
+impl Tokens {
+    pub fn read(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("can't read tokens '{}': {}", path, err))?;
+
+        let mut tokens: Vec<String> = Vec::new();
+
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let bad = |msg: &str| format!("{} line {}: {}", path, n + 1, msg);
+            let token = line.to_lowercase();
+
+            if token.chars().any(char::is_whitespace) {
+                return Err(bad("a token can't contain whitespace"));
+            }
+            if tokens.contains(&token) {
+                return Err(bad(&format!("'{}' is already a token", token)));
+            }
+            if tokens.len() == MAX_SYMBOLS {
+                return Err(bad(&format!("there can be at most {} tokens", MAX_SYMBOLS)));
+            }
+
+            tokens.push(token);
+        }
+
+        if tokens.is_empty() {
+            return Err(format!("{} defines no tokens", path));
+        }
+
+        let mut by_length: Vec<usize> = (0..tokens.len()).collect();
+        by_length.sort_by_key(|&i| std::cmp::Reverse(tokens[i].len()));
+
+        Ok(Tokens { tokens, by_length })
+    }
+
+    // the letters standing in for the tokens
+    //
+    pub fn letters(&self) -> String {
+        (0..self.tokens.len()).map(stand_in).collect()
+    }
+
+    // every line of `text` split into tokens, and written as their
+    // stand-in letters (dropping the lines that don't split)
+    //
+    pub fn encode(&self, text: &str) -> String {
+        let mut encoded = String::with_capacity(text.len());
+
+        for letters in text.lines().filter_map(|line| self.encode_word(line)) {
+            encoded.push_str(&letters);
+            encoded.push('\n');
+        }
+        encoded
+    }
+
+    // one word written as stand-in letters, if it splits into tokens
+    //
+    pub fn encode_word(&self, word: &str) -> Option<String> {
+        let mut letters = String::new();
+
+        self.split(&word.trim().to_lowercase(), &mut letters)
+            .then_some(letters)
+    }
+
+    // split `word` into tokens, pushing their letters onto `letters`,
+    // trying the longer tokens first at each point but backing off to
+    // shorter ones when the rest of the word won't split after them
+    //
+    fn split(&self, word: &str, letters: &mut String) -> bool {
+        if word.is_empty() {
+            return !letters.is_empty();
+        }
+
+        for &i in &self.by_length {
+            if let Some(rest) = word.strip_prefix(&*self.tokens[i]) {
+                letters.push(stand_in(i));
+
+                if self.split(rest, letters) {
+                    return true;
+                }
+                letters.pop();
+            }
+        }
+        false
+    }
+
+    pub fn token(&self, letter: ZChar) -> &str {
+        &self.tokens[letter.ord()]
+    }
+
+    pub fn spell(&self, word: &ZWord) -> Spelled<'_> {
+        Spelled {
+            tokens: self,
+            letters: *word,
+            sep: "",
+        }
+    }
+
+    // a set of tokens rather than a word, so spaced apart
+    //
+    pub fn list(&self, letters: &[ZChar]) -> Spelled<'_> {
+        Spelled {
+            tokens: self,
+            letters: letters.iter().copied().collect(),
+            sep: " ",
+        }
+    }
+
+    // the definition itself, for fingerprinting a run
+    //
+    pub fn source(&self) -> String {
+        self.tokens.join(" ")
+    }
+
+    // the tokens, in the order of the letters standing in for them
+    //
+    pub fn all(&self) -> &[String] {
+        &self.tokens
+    }
+}
+
+// the letter standing in for the `i`th token
+//
+fn stand_in(i: usize) -> char {
+    ZChar::from_ord(i).unwrap().chr()
+}
+
+// some stand-in letters, written in tokens
+//
+pub struct Spelled<'a> {
+    tokens: &'a Tokens,
+    letters: ZWord,
+    sep: &'static str,
+}
+
+impl Display for Spelled<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, z) in self.letters.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", self.sep)?;
+            }
+            write!(f, "{}", self.tokens.token(z))?;
+        }
+        Ok(())
+    }
+}
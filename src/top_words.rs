@@ -0,0 +1,91 @@
+use std::collections::{HashMap, HashSet};
+
+use fivewords::{PreparedDict, Solver, ZWord};
+
+// --top-words N: solve over only the N most frequent candidates of a
+// dictionary annotated with frequencies, each line a word and then its
+// count (as most frequency lists are laid out):
+//
+//   the 23135851162
+//   of 13151942776
+//   ...
+//
+// The counts are dropped to read the words as usual, the candidates
+// ranked by them (a set of letters counting as its most frequent
+// spelling, and ties going to the one the dictionary lists first), and
+// the lines spelling anything but the top N left out. A line with no
+// count counts as 0.
+//
+pub fn keep(solver: &Solver, text: &str, n: usize) -> Result<String, String> {
+    let mut words = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let mut fields = line.split_whitespace();
+
+        let Some(word) = fields.next() else {
+            continue;
+        };
+
+        let count = match fields.next() {
+            Some(count) => count
+                .parse::<u64>()
+                .map_err(|_| format!("line {}: {:?} isn't a count", i + 1, count))?,
+            None => 0,
+        };
+
+        if fields.next().is_some() {
+            return Err(format!(
+                "line {}: expected a word and its count, not {:?}",
+                i + 1,
+                line.trim()
+            ));
+        }
+
+        words.push((word, count));
+    }
+
+    // each set of letters at its most frequent
+    //
+    let mut counts: HashMap<u32, u64> = HashMap::new();
+
+    for &(word, count) in &words {
+        if let Ok(letters) = solver.parse_word(word) {
+            let top = counts.entry(letters_of(&letters)).or_default();
+            *top = (*top).max(count);
+        }
+    }
+
+    let stripped: String = words
+        .iter()
+        .map(|(word, _)| format!("{}\n", word))
+        .collect();
+
+    let mut ranked: Vec<u32> = solver
+        .candidates(&PreparedDict::new(&stripped))
+        .iter()
+        .map(letters_of)
+        .collect();
+
+    ranked.sort_by_key(|letters| std::cmp::Reverse(counts.get(letters).copied().unwrap_or(0)));
+    ranked.truncate(n);
+
+    let top: HashSet<u32> = ranked.into_iter().collect();
+
+    let kept: Vec<&str> = words
+        .iter()
+        .map(|&(word, _)| word)
+        .filter(|word| {
+            solver
+                .parse_word(word)
+                .is_ok_and(|letters| top.contains(&letters_of(&letters)))
+        })
+        .collect();
+
+    Ok(kept.iter().map(|word| format!("{}\n", word)).collect())
+}
+
+// a word's letters, as a plain (bit 0 = 'a') mask
+//
+fn letters_of(word: &ZWord) -> u32 {
+    word.iter().fold(0, |acc, z| acc | 1 << z.ord())
+}
@@ -0,0 +1,214 @@
+use core::fmt::{Debug, Display, Formatter};
+use core::iter::{Copied, Take};
+use core::{array, slice};
+
+use crate::index::ALPHABET_LEN;
+use crate::prepared::{read_letters, Problem};
+
+const U8A: u8 = b'a';
+
+// the symbols past 'z' are written as the characters of the Private
+// Use Area from U+E000 on, which no dictionary of words will use
+//
+const PAST_Z: u32 = 0xe000;
+
+/// The most symbols an alphabet can have: the 26 letters, and up to
+/// 38 more past 'z'.
+pub const MAX_SYMBOLS: usize = 64;
+
+/// A single letter, stored as its offset from `'a'`. An alphabet of
+/// more than the 26 letters (the tokens of a syllabary, say) carries
+/// on past 'z', each further symbol written as a character of the
+/// Private Use Area: U+E000 for the 27th, U+E001 for the 28th, and so
+/// on.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ZChar(pub(crate) u8);
+
+impl ZChar {
+    pub(crate) fn from(c: char) -> Self {
+        match c.is_ascii_alphabetic() {
+            true => ZChar((c.to_ascii_lowercase() as u8) - U8A),
+            false => ZChar((c as u32 - PAST_Z) as u8 + ALPHABET_LEN as u8),
+        }
+    }
+
+    /// The symbol at position `ord` of the alphabet ('a' at 0), if an
+    /// alphabet can have that many.
+    pub fn from_ord(ord: usize) -> Option<Self> {
+        (ord < MAX_SYMBOLS).then_some(ZChar(ord as u8))
+    }
+
+    /// The (lowercase) letter, or the character of a symbol past 'z'.
+    pub fn chr(&self) -> char {
+        match self.ord() < ALPHABET_LEN {
+            true => (self.0 + U8A) as char,
+            false => char::from_u32(PAST_Z + (self.ord() - ALPHABET_LEN) as u32).unwrap(),
+        }
+    }
+
+    pub(crate) fn mask(&self) -> u64 {
+        1 << self.0
+    }
+
+    /// The letter's position in the alphabet, from 0.
+    pub fn ord(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl TryFrom<char> for ZChar {
+    type Error = char;
+
+    /// Any ASCII letter, in either case, or the character of a symbol
+    /// past 'z'; anything else is handed back.
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        let past_z = PAST_Z..PAST_Z + (MAX_SYMBOLS - ALPHABET_LEN) as u32;
+
+        if c.is_ascii_alphabetic() || past_z.contains(&(c as u32)) {
+            Ok(ZChar::from(c))
+        } else {
+            Err(c)
+        }
+    }
+}
+
+impl Display for ZChar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.chr())
+    }
+}
+
+// the letter, and its offset as stored, as in ZChar('q', 16)
+//
+impl Debug for ZChar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ZChar")
+            .field(&self.chr())
+            .field(&self.0)
+            .finish()
+    }
+}
+
+// the letters of a word, stored inline so the type stays `Copy`. As
+// every letter must be distinct, no word is longer than the alphabet.
+//
+/// A word made of distinct letters.
+#[derive(Copy, Clone)]
+pub struct ZWord {
+    len: u8,
+    chars: [ZChar; MAX_SYMBOLS],
+}
+
+impl Default for ZWord {
+    fn default() -> Self {
+        ZWord {
+            len: 0,
+            chars: [ZChar::default(); MAX_SYMBOLS],
+        }
+    }
+}
+
+impl ZWord {
+    pub(crate) fn push(&mut self, z: ZChar) {
+        self.chars[self.len as usize] = z;
+        self.len += 1;
+    }
+
+    /// The number of letters.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The letters, in spelling order.
+    pub fn iter(&self) -> Copied<slice::Iter<'_, ZChar>> {
+        self.chars[..self.len as usize].iter().copied()
+    }
+}
+
+impl FromIterator<ZChar> for ZWord {
+    /// Spell a word with the letters, in order. Any number of letters
+    /// makes a word, as long as none repeats (so there are at most
+    /// [`MAX_SYMBOLS`]): a repeated letter panics.
+    fn from_iter<I: IntoIterator<Item = ZChar>>(letters: I) -> Self {
+        let mut word = ZWord::default();
+        let mut bits = 0;
+
+        for z in letters {
+            assert!(bits & z.mask() == 0, "'{}' repeats in a ZWord", z);
+
+            bits |= z.mask();
+            word.push(z);
+        }
+        word
+    }
+}
+
+impl TryFrom<&str> for ZWord {
+    type Error = Problem;
+
+    /// A word of distinct ASCII letters, in either case. Unlike
+    /// collecting letters, a repeated letter is an error here, as is
+    /// anything that isn't a letter.
+    fn try_from(spelling: &str) -> Result<Self, Self::Error> {
+        read_letters(spelling).map(|(word, _)| word)
+    }
+}
+
+impl IntoIterator for ZWord {
+    type Item = ZChar;
+    type IntoIter = Take<array::IntoIter<ZChar, MAX_SYMBOLS>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chars.into_iter().take(self.len as usize)
+    }
+}
+
+impl<'a> IntoIterator for &'a ZWord {
+    type Item = ZChar;
+    type IntoIter = Copied<slice::Iter<'a, ZChar>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for ZWord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for z in self {
+            write!(f, "{}", z)?;
+        }
+        Ok(())
+    }
+}
+
+// the spelling, and the letters' offsets as stored, as in
+// ZWord("crane", [2, 17, 0, 13, 4])
+//
+impl Debug for ZWord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        struct Spelling<'a>(&'a ZWord);
+
+        impl Debug for Spelling<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                write!(f, "\"{}\"", self.0)
+            }
+        }
+
+        struct Offsets<'a>(&'a ZWord);
+
+        impl Debug for Offsets<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self.0.iter().map(|z| z.0)).finish()
+            }
+        }
+
+        f.debug_tuple("ZWord")
+            .field(&Spelling(self))
+            .field(&Offsets(self))
+            .finish()
+    }
+}
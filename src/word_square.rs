@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use fivewords::{PreparedDict, Solution, ZWord};
+
+// --word-square: arrangements of a solution's words, one per row, whose
+// columns read down as dictionary words too. Position matters, so any
+// spelling of a row's letters may stand in for the one the solution
+// shows, in any order of the rows.
+//
+pub struct Squares {
+    // every spelling of each set of letters (keyed by the letters in
+    // alphabetical order)
+    //
+    spellings: HashMap<String, Vec<String>>,
+
+    // every prefix of the dictionary words a column could be (as long
+    // as the set size), the whole words included
+    //
+    prefixes: HashSet<String>,
+}
+
+fn letters(word: &ZWord) -> String {
+    let mut letters: Vec<char> = word.iter().map(|z| z.chr()).collect();
+
+    letters.sort_unstable();
+    letters.into_iter().collect()
+}
+
+impl Squares {
+    pub fn new(dict: &PreparedDict, set_size: usize) -> Self {
+        let mut spellings: HashMap<String, Vec<String>> = HashMap::new();
+        let mut prefixes = HashSet::new();
+
+        for word in dict.words() {
+            let spelling = word.to_string();
+
+            if word.len() == set_size {
+                for end in 1..=set_size {
+                    prefixes.insert(spelling[..end].to_string());
+                }
+            }
+
+            let all = spellings.entry(letters(word)).or_default();
+
+            if !all.contains(&spelling) {
+                all.push(spelling);
+            }
+        }
+
+        Squares {
+            spellings,
+            prefixes,
+        }
+    }
+
+    // every word square the solution's words make, as its rows
+    //
+    pub fn find(&self, solution: &Solution) -> Vec<Vec<String>> {
+        let rows: Vec<&[String]> = solution
+            .words()
+            .iter()
+            .map(|word| {
+                self.spellings
+                    .get(&letters(word))
+                    .map_or(&[][..], Vec::as_slice)
+            })
+            .collect();
+
+        let width = solution.words().first().map_or(0, ZWord::len);
+        let mut squares = Vec::new();
+
+        self.extend(
+            &rows,
+            &mut vec![String::new(); width],
+            &mut Vec::new(),
+            &mut squares,
+        );
+        squares
+    }
+
+    // place each unplaced word in the next row, in each of its
+    // spellings, as long as every column still starts a word
+    //
+    fn extend<'a>(
+        &self,
+        rows: &[&'a [String]],
+        columns: &mut Vec<String>,
+        placed: &mut Vec<(usize, &'a String)>,
+        squares: &mut Vec<Vec<String>>,
+    ) {
+        if placed.len() == rows.len() {
+            squares.push(placed.iter().map(|(_, row)| row.to_string()).collect());
+            return;
+        }
+
+        for (i, spellings) in rows.iter().enumerate() {
+            if placed.iter().any(|&(j, _)| j == i) {
+                continue;
+            }
+
+            for spelling in *spellings {
+                for (column, c) in columns.iter_mut().zip(spelling.chars()) {
+                    column.push(c);
+                }
+
+                if columns.iter().all(|column| self.prefixes.contains(column)) {
+                    placed.push((i, spelling));
+                    self.extend(rows, columns, placed, squares);
+                    placed.pop();
+                }
+
+                for column in columns.iter_mut() {
+                    column.pop();
+                }
+            }
+        }
+    }
+}
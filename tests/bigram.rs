@@ -0,0 +1,64 @@
+// forbidden bigrams drop a spelling before anagrams are collapsed, so
+// another spelling of the same letters still makes the solution (the
+// words of which are sorted, to not depend on the search's order)
+//
+use fivewords::{Explanation, SolverBuilder};
+
+const DICT: &str = "qib\nbiq\nxyz\n";
+
+fn solve(forbidden: &[&str]) -> Vec<String> {
+    let mut builder = SolverBuilder::new()
+        .word_len(3)
+        .set_size(2)
+        .allowed_letters("bqixyz");
+
+    for bigram in forbidden {
+        builder = builder.forbid_bigram(bigram);
+    }
+
+    builder
+        .build()
+        .unwrap()
+        .solve(DICT)
+        .iter()
+        .map(|solution| {
+            let mut words: Vec<String> = solution.words().iter().map(|w| w.to_string()).collect();
+            words.sort();
+            words.join(" ")
+        })
+        .collect()
+}
+
+#[test]
+fn an_anagram_without_the_bigram_stands_in() {
+    assert_eq!(solve(&[]), ["qib xyz"]);
+    assert_eq!(solve(&["qi"]), ["biq xyz"]);
+    assert_eq!(solve(&["QI"]), ["biq xyz"]);
+}
+
+#[test]
+fn a_word_with_no_other_spelling_is_dropped() {
+    assert!(solve(&["yz"]).is_empty());
+    assert!(solve(&["qi", "iq"]).is_empty());
+}
+
+#[test]
+fn explain_names_the_bigram() {
+    let solver = SolverBuilder::new()
+        .word_len(3)
+        .set_size(2)
+        .allowed_letters("bqixyz")
+        .forbid_bigram("qi")
+        .build()
+        .unwrap();
+    let dict = solver.prepare(DICT);
+
+    assert_eq!(
+        solver.explain(&dict, "qib"),
+        Explanation::ForbiddenBigram("qi".to_string())
+    );
+    assert!(matches!(
+        solver.explain(&dict, "biq"),
+        Explanation::Solutions { count: 1, .. }
+    ));
+}
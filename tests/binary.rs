@@ -0,0 +1,54 @@
+// a --format binary stream is a 12-byte header then a 21-byte record
+// per solution (five letter masks and the unused letter), and
+// --decode-binary reads it back as the --show-unused text of the same
+// solve
+//
+use std::fs;
+use std::process::Command;
+
+fn fivewords(args: &[&str]) -> Vec<u8> {
+    let output = Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(args)
+        .args(["--max-depth-trace", "0", "--no-warn"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{:?}", args);
+    output.stdout
+}
+
+fn sorted_lines(bytes: Vec<u8>) -> Vec<String> {
+    let mut lines: Vec<String> = String::from_utf8(bytes)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.starts_with("Elapsed"))
+        .map(str::to_string)
+        .collect();
+
+    lines.sort();
+    lines
+}
+
+#[test]
+fn binary_round_trips_to_text() {
+    let dict = "words.txt";
+    let stream = fivewords(&["--format", "binary", dict]);
+    let text = sorted_lines(fivewords(&["--show-unused", dict]));
+
+    assert!(!text.is_empty());
+    assert_eq!(&stream[..4], b"FWB1");
+    assert_eq!(stream[4..12], [5, 0, 0, 0, 5, 0, 0, 0]);
+    assert_eq!(stream.len(), 12 + 21 * text.len());
+
+    let path = std::env::temp_dir().join(format!("fivewords-binary-{}.bin", std::process::id()));
+    fs::write(&path, &stream).unwrap();
+
+    let decoded = sorted_lines(fivewords(&[
+        "--decode-binary",
+        path.to_str().unwrap(),
+        dict,
+    ]));
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(decoded, text);
+}
@@ -0,0 +1,467 @@
+// checks the bucketed search against a brute-force enumeration of
+// every combination of candidate words, on small random dictionaries
+//
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use fivewords::{Backend, SolverBuilder};
+
+// a tiny deterministic PRNG (xorshift), so failures are reproducible
+// without pulling in a dependency
+//
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn mask(word: &str) -> u32 {
+    word.bytes().fold(0, |acc, b| acc | 1 << (b - b'a'))
+}
+
+// random words of distinct letters, with a few full solutions planted
+// so there is always something to find
+//
+fn dictionary(rng: &mut Rng, word_len: usize, set_size: usize, words: usize) -> Vec<String> {
+    let mut dict = Vec::new();
+    let mut letters: Vec<u8> = (b'a'..=b'z').collect();
+
+    for _ in 0..3 {
+        rng.shuffle(&mut letters);
+
+        for word in letters.chunks(word_len).take(set_size) {
+            dict.push(String::from_utf8(word.to_vec()).unwrap());
+        }
+    }
+
+    while dict.len() < words {
+        rng.shuffle(&mut letters);
+        dict.push(String::from_utf8(letters[..word_len].to_vec()).unwrap());
+    }
+
+    rng.shuffle(&mut dict);
+    dict
+}
+
+// every set of `set_size` words with pairwise disjoint letters, with
+// anagrams collapsed onto their first spelling like the solver does
+//
+fn brute_force(dict: &[String], set_size: usize) -> HashSet<Vec<String>> {
+    let mut seen = HashSet::new();
+    let words: Vec<&String> = dict.iter().filter(|w| seen.insert(mask(w))).collect();
+
+    let mut found = HashSet::new();
+    let mut picked = Vec::new();
+
+    fn combine<'a>(
+        words: &[&'a String],
+        start: usize,
+        used: u32,
+        set_size: usize,
+        picked: &mut Vec<&'a String>,
+        found: &mut HashSet<Vec<String>>,
+    ) {
+        if picked.len() == set_size {
+            let mut set: Vec<String> = picked.iter().map(|w| w.to_string()).collect();
+            set.sort();
+            found.insert(set);
+            return;
+        }
+
+        for i in start..words.len() {
+            let bits = mask(words[i]);
+
+            if used & bits == 0 {
+                picked.push(words[i]);
+                combine(words, i + 1, used | bits, set_size, picked, found);
+                picked.pop();
+            }
+        }
+    }
+
+    combine(&words, 0, 0, set_size, &mut picked, &mut found);
+    found
+}
+
+fn check(seed: u64, word_len: usize, set_size: usize, words: usize) {
+    let mut rng = Rng(seed);
+    let dict = dictionary(&mut rng, word_len, set_size, words);
+
+    let solve = |backend| {
+        SolverBuilder::new()
+            .word_len(word_len)
+            .set_size(set_size)
+            .backend(backend)
+            .build()
+            .unwrap()
+            .solve(&dict.join("\n"))
+    };
+
+    let solutions = solve(Backend::Recursive);
+
+    let searched: HashSet<Vec<String>> = solutions
+        .iter()
+        .map(|solution| {
+            let mut set: Vec<String> = solution.words().iter().map(|w| w.to_string()).collect();
+            set.sort();
+            set
+        })
+        .collect();
+
+    assert_eq!(
+        solutions.len(),
+        searched.len(),
+        "duplicate solutions (seed {}, {}x{})",
+        seed,
+        word_len,
+        set_size
+    );
+    assert_eq!(
+        searched,
+        brute_force(&dict, set_size),
+        "seed {}, {}x{}",
+        seed,
+        word_len,
+        set_size
+    );
+
+    // nor the same set reached again in another order, whichever
+    // threads find it
+    //
+    let ids: HashSet<u64> = solutions.iter().map(|solution| solution.id()).collect();
+    let threaded = SolverBuilder::new()
+        .word_len(word_len)
+        .set_size(set_size)
+        .threads(3)
+        .build()
+        .unwrap()
+        .solve(&dict.join("\n"));
+
+    assert_eq!(
+        ids.len(),
+        solutions.len(),
+        "reordered duplicates (seed {}, {}x{})",
+        seed,
+        word_len,
+        set_size
+    );
+    assert_eq!(
+        threaded
+            .iter()
+            .map(|solution| solution.id())
+            .collect::<HashSet<_>>(),
+        ids,
+        "threads differ (seed {}, {}x{})",
+        seed,
+        word_len,
+        set_size
+    );
+    assert_eq!(threaded.len(), solutions.len());
+
+    // the backends walk the same tree, so agree on the order too
+    //
+    let iterative: Vec<String> = solve(Backend::Iterative)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let recursive: Vec<String> = solutions.iter().map(ToString::to_string).collect();
+
+    assert_eq!(
+        iterative, recursive,
+        "backends differ (seed {}, {}x{})",
+        seed, word_len, set_size
+    );
+}
+
+#[test]
+fn five_by_five_matches_brute_force() {
+    for seed in 1..=8 {
+        check(seed, 5, 5, 60);
+    }
+}
+
+// several spare letters, so the search skips more than one bit
+//
+#[test]
+fn four_by_five_matches_brute_force() {
+    for seed in 1..=8 {
+        check(seed, 4, 5, 60);
+    }
+}
+
+#[test]
+fn three_by_eight_matches_brute_force() {
+    for seed in 1..=4 {
+        check(seed, 3, 8, 50);
+    }
+}
+
+// a deep search: a dozen words to a solution
+//
+#[test]
+fn two_by_twelve_matches_brute_force() {
+    for seed in 1..=4 {
+        check(seed, 2, 12, 40);
+    }
+}
+
+// words of several lengths, with a few sets planted that use as many of
+// the longest words as fit (covering the whole alphabet, where that
+// adds up)
+//
+fn mixed_dictionary(
+    rng: &mut Rng,
+    lengths: RangeInclusive<usize>,
+    set_size: usize,
+    words: usize,
+) -> Vec<String> {
+    let (min, max) = (*lengths.start(), *lengths.end());
+    let longest = (0..=set_size)
+        .rev()
+        .find(|&n| n * max + (set_size - n) * min <= 26)
+        .unwrap();
+
+    let mut dict = Vec::new();
+    let mut letters: Vec<u8> = (b'a'..=b'z').collect();
+
+    for _ in 0..3 {
+        rng.shuffle(&mut letters);
+
+        let mut rest = &letters[..];
+
+        for n in 0..set_size {
+            let (word, after) = rest.split_at(if n < longest { max } else { min });
+
+            dict.push(String::from_utf8(word.to_vec()).unwrap());
+            rest = after;
+        }
+    }
+
+    while dict.len() < words {
+        let len = min + rng.below(max - min + 1);
+
+        rng.shuffle(&mut letters);
+        dict.push(String::from_utf8(letters[..len].to_vec()).unwrap());
+    }
+
+    rng.shuffle(&mut dict);
+    dict
+}
+
+fn check_mixed(seed: u64, lengths: RangeInclusive<usize>, set_size: usize, words: usize) {
+    let mut rng = Rng(seed);
+    let dict = mixed_dictionary(&mut rng, lengths.clone(), set_size, words);
+
+    let solve = |threads| {
+        let mut builder = SolverBuilder::new()
+            .word_len_range(lengths.clone())
+            .set_size(set_size);
+
+        if let Some(threads) = threads {
+            builder = builder.threads(threads);
+        }
+
+        let solutions = builder.build().unwrap().solve(&dict.join("\n"));
+        let sets: HashSet<Vec<String>> = solutions
+            .iter()
+            .map(|solution| {
+                let mut set: Vec<String> = solution.words().iter().map(|w| w.to_string()).collect();
+                set.sort();
+                set
+            })
+            .collect();
+
+        assert_eq!(
+            solutions.len(),
+            sets.len(),
+            "duplicate solutions (seed {}, {:?}x{})",
+            seed,
+            lengths,
+            set_size
+        );
+        sets
+    };
+
+    let expected = brute_force(&dict, set_size);
+
+    assert_eq!(
+        solve(None),
+        expected,
+        "seed {}, {:?}x{}",
+        seed,
+        lengths,
+        set_size
+    );
+    assert_eq!(
+        solve(Some(3)),
+        expected,
+        "seed {}, {:?}x{}",
+        seed,
+        lengths,
+        set_size
+    );
+}
+
+#[test]
+fn mixed_lengths_match_brute_force() {
+    for seed in 1..=6 {
+        check_mixed(seed, 4..=6, 4, 60);
+    }
+}
+
+// eight three-letter words and a two-letter one use every letter, so
+// some solutions leave none unused
+//
+#[test]
+fn mixed_lengths_covering_the_alphabet_match_brute_force() {
+    for seed in 1..=4 {
+        check_mixed(seed, 2..=3, 9, 45);
+    }
+}
+
+// every set of `set_size` words (anagrams collapsed) covering at least
+// `min` letters between them, sharing letters or not
+//
+fn brute_force_cover(
+    dict: &[String],
+    set_size: usize,
+    unique: RangeInclusive<u32>,
+) -> HashSet<Vec<String>> {
+    let mut seen = HashSet::new();
+    let words: Vec<&String> = dict.iter().filter(|w| seen.insert(mask(w))).collect();
+
+    let mut found = HashSet::new();
+    let mut picked = Vec::new();
+
+    fn combine<'a>(
+        words: &[&'a String],
+        start: usize,
+        used: u32,
+        (set_size, unique): (usize, &RangeInclusive<u32>),
+        picked: &mut Vec<&'a String>,
+        found: &mut HashSet<Vec<String>>,
+    ) {
+        if picked.len() == set_size {
+            if unique.contains(&used.count_ones()) {
+                let mut set: Vec<String> = picked.iter().map(|w| w.to_string()).collect();
+                set.sort();
+                found.insert(set);
+            }
+            return;
+        }
+
+        for i in start..words.len() {
+            picked.push(words[i]);
+            combine(
+                words,
+                i + 1,
+                used | mask(words[i]),
+                (set_size, unique),
+                picked,
+                found,
+            );
+            picked.pop();
+        }
+    }
+
+    combine(&words, 0, 0, (set_size, &unique), &mut picked, &mut found);
+    found
+}
+
+fn check_cover(
+    seed: u64,
+    word_len: usize,
+    set_size: usize,
+    unique: RangeInclusive<usize>,
+    words: usize,
+) {
+    let mut rng = Rng(seed);
+    let dict = dictionary(&mut rng, word_len, set_size, words);
+
+    let solutions = SolverBuilder::new()
+        .word_len(word_len)
+        .set_size(set_size)
+        .unique_range(unique.clone())
+        .build()
+        .unwrap()
+        .solve(&dict.join("\n"));
+
+    let searched: HashSet<Vec<String>> = solutions
+        .iter()
+        .map(|solution| {
+            let mut set: Vec<String> = solution.words().iter().map(|w| w.to_string()).collect();
+            set.sort();
+            set
+        })
+        .collect();
+
+    assert_eq!(
+        solutions.len(),
+        searched.len(),
+        "duplicate solutions (seed {}, {}x{}, {:?} letters)",
+        seed,
+        word_len,
+        set_size,
+        unique
+    );
+    assert_eq!(
+        searched,
+        brute_force_cover(
+            &dict,
+            set_size,
+            *unique.start() as u32..=*unique.end() as u32
+        ),
+        "seed {}, {}x{}, {:?} letters",
+        seed,
+        word_len,
+        set_size,
+        unique
+    );
+}
+
+// words sharing a letter or two still make a set
+//
+#[test]
+fn overlapping_sets_match_brute_force() {
+    for seed in 1..=6 {
+        check_cover(seed, 5, 4, 18..=26, 40);
+        check_cover(seed, 4, 5, 17..=26, 30);
+        check_cover(seed, 3, 3, 9..=26, 40);
+    }
+}
+
+// enough shared letters that a word may add none of its own
+//
+#[test]
+fn sets_with_covered_words_match_brute_force() {
+    for seed in 1..=6 {
+        check_cover(seed, 3, 3, 6..=26, 25);
+        check_cover(seed, 2, 4, 5..=26, 25);
+    }
+}
+
+// covering no more letters than asked, too
+//
+#[test]
+fn sets_covering_a_range_match_brute_force() {
+    for seed in 1..=6 {
+        check_cover(seed, 5, 4, 18..=18, 40);
+        check_cover(seed, 4, 5, 17..=18, 30);
+        check_cover(seed, 3, 3, 6..=7, 25);
+    }
+}
@@ -0,0 +1,100 @@
+// every candidate is in exactly one bucket: its rarest letter's
+//
+// (fivewords::buckets is a hidden, unstable hook into the index, there
+// just for these tests)
+//
+use fivewords::{SolverBuilder, ZChar, ZWord};
+
+#[test]
+fn buckets_hold_every_candidate_once() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let solver = SolverBuilder::new().build().unwrap();
+    let prepared = solver.prepare(&dict);
+
+    let sizes = solver.bucket_sizes(&prepared);
+    let letters = sizes.iter().map(|(letter, _)| letter.chr());
+
+    assert_eq!(sizes.len(), 26);
+    assert_eq!(letters.collect::<std::collections::HashSet<_>>().len(), 26);
+    assert_eq!(
+        sizes.iter().map(|(_, size)| size).sum::<usize>(),
+        solver.candidates(&prepared).len()
+    );
+}
+
+fn order(letters: &str) -> [ZChar; 26] {
+    let letters: Vec<_> = letters
+        .chars()
+        .map(|c| ZChar::try_from(c).unwrap())
+        .collect();
+
+    letters.try_into().unwrap()
+}
+
+fn words(words: &[&str]) -> Vec<ZWord> {
+    words.iter().map(|&w| ZWord::try_from(w).unwrap()).collect()
+}
+
+fn spelled(bucket: &[ZWord]) -> Vec<String> {
+    bucket.iter().map(ZWord::to_string).collect()
+}
+
+#[test]
+fn words_go_in_their_rarest_letters_bucket() {
+    let order = order("qzjxkvbpgwyfmcuhldontsirae");
+    let buckets = fivewords::buckets(
+        &words(&["quick", "brown", "foxes", "jumpy", "lazed"]),
+        &order,
+    );
+
+    assert_eq!(buckets.len(), 26);
+    assert_eq!(spelled(&buckets[0]), ["quick"]);
+    assert_eq!(spelled(&buckets[1]), ["lazed"]);
+    assert_eq!(spelled(&buckets[2]), ["jumpy"]);
+    assert_eq!(spelled(&buckets[3]), ["foxes"]);
+    assert_eq!(spelled(&buckets[6]), ["brown"]);
+    assert_eq!(buckets.iter().map(Vec::len).sum::<usize>(), 5);
+}
+
+#[test]
+fn buckets_keep_the_words_in_order() {
+    let order = order("abcdefghijklmnopqrstuvwxyz");
+    let buckets = fivewords::buckets(&words(&["tick", "crest", "bumps", "chirp", "cafe"]), &order);
+
+    assert_eq!(spelled(&buckets[0]), ["cafe"]);
+    assert_eq!(spelled(&buckets[1]), ["bumps"]);
+    assert_eq!(spelled(&buckets[2]), ["tick", "crest", "chirp"]);
+}
+
+#[test]
+fn the_order_decides_the_buckets() {
+    let words = words(&["crane", "slate"]);
+
+    let alphabetical = fivewords::buckets(&words, &order("abcdefghijklmnopqrstuvwxyz"));
+    let reversed = fivewords::buckets(&words, &order("zyxwvutsrqponmlkjihgfedcba"));
+
+    assert_eq!(spelled(&alphabetical[0]), ["crane", "slate"]);
+    assert_eq!(spelled(&reversed[25 - 19]), ["slate"]);
+    assert_eq!(spelled(&reversed[25 - 17]), ["crane"]);
+}
+
+#[test]
+fn buckets_agree_with_the_solvers_sizes() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let solver = SolverBuilder::new().build().unwrap();
+    let prepared = solver.prepare(&dict);
+
+    let sizes = solver.bucket_sizes(&prepared);
+    let order = sizes.iter().map(|&(letter, _)| letter).collect::<Vec<_>>();
+    let buckets = fivewords::buckets(&solver.candidates(&prepared), &order.try_into().unwrap());
+
+    for ((letter, size), bucket) in sizes.iter().zip(&buckets) {
+        assert_eq!(*size, bucket.len(), "bucket '{}'", letter.chr());
+    }
+}
+
+#[test]
+#[should_panic(expected = "every letter once")]
+fn an_order_repeating_a_letter_is_rejected() {
+    fivewords::buckets(&words(&["crane"]), &order("aacdefghijklmnopqrstuvwxyz"));
+}
@@ -0,0 +1,69 @@
+// the candidates a solve indexes, each with its letters' plain mask,
+// and those masks decoded back to letters
+//
+use fivewords::{PreparedDict, SolverBuilder};
+
+#[test]
+fn candidates_are_the_distinct_words_with_their_masks() {
+    let dict = "crane\nfloor\nnacre\ncab\nBacks\nzebra\ncrane\n";
+
+    let found: Vec<_> = fivewords::candidates(dict, 5).collect();
+
+    assert_eq!(
+        found,
+        [
+            ("crane".to_string(), 0b10_0010_0000_0001_0101),
+            ("backs".to_string(), 0b100_0000_0100_0000_0111),
+            ("zebra".to_string(), 0b10_0000_0010_0000_0000_0001_0011),
+        ]
+    );
+    assert_eq!(
+        fivewords::candidates(dict, 3).collect::<Vec<_>>(),
+        [("cab".to_string(), 0b111)]
+    );
+}
+
+#[test]
+fn they_match_what_a_solve_indexes() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let words: Vec<&str> = dict.lines().collect();
+
+    for len in [4, 5, 7] {
+        let found: Vec<(String, u32)> = fivewords::candidates(&dict, len).collect();
+        let expected: Vec<String> = SolverBuilder::new()
+            .word_len(len)
+            .set_size(1)
+            .build()
+            .unwrap()
+            .candidates(&PreparedDict::new(&dict))
+            .iter()
+            .map(|word| word.to_string())
+            .collect();
+
+        assert_eq!(found.len(), fivewords::count_candidates(&words, len));
+        assert!(found.iter().map(|(word, _)| word).eq(expected.iter()));
+
+        for (word, bits) in &found {
+            let mask = word.bytes().fold(0, |acc, b| acc | 1 << (b - b'a'));
+
+            assert_eq!(*bits, mask, "{}", word);
+            assert_eq!(bits.count_ones() as usize, len);
+        }
+    }
+}
+
+#[test]
+fn masks_decode_to_their_letters() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    for (word, bits) in fivewords::candidates(&dict, 5) {
+        let mut letters: Vec<char> = word.chars().collect();
+        letters.sort();
+
+        assert_eq!(fivewords::decode_mask(bits), letters, "{}", word);
+    }
+
+    assert_eq!(fivewords::decode_mask(0), []);
+    assert_eq!(fivewords::decode_mask(u32::MAX).len(), 26);
+    assert_eq!(fivewords::decode_mask(1 << 25 | 1 << 26 | 1), ['a', 'z']);
+}
@@ -0,0 +1,130 @@
+// a --resume checkpoint is only picked up again by a run with the same
+// parameters, down to the contents of its --freq-source corpus, and
+// picks up any kind of output, --grid squares of several lines too
+//
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn fivewords(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(args)
+        .args(["--max-depth-trace", "0", "--no-warn"])
+        .output()
+        .unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("fivewords-{}-{}", std::process::id(), name))
+}
+
+fn solutions(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.starts_with("Elapsed"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn a_changed_corpus_is_a_different_run() {
+    let checkpoint = temp_path("freq-checkpoint");
+    let corpus = temp_path("corpus.txt");
+    let dict = temp_path("freq-dict.txt");
+    let args = [
+        "--resume",
+        checkpoint.to_str().unwrap(),
+        "--freq-source",
+        corpus.to_str().unwrap(),
+        "--len",
+        "3",
+        "--count",
+        "2",
+        dict.to_str().unwrap(),
+    ];
+
+    let _ = fs::remove_file(&checkpoint);
+    fs::write(&dict, "abc\ndef\nghi\nadg\n").unwrap();
+    fs::write(&corpus, "quiz jazz fizz\n").unwrap();
+    let first = fivewords(&args);
+
+    // (the same corpus resumes, or here replays, the run)
+    //
+    let replayed = fivewords(&args);
+
+    fs::write(&corpus, "eerie eerie\n").unwrap();
+    let changed = fivewords(&args);
+
+    fs::remove_file(&checkpoint).unwrap();
+    fs::remove_file(&corpus).unwrap();
+    fs::remove_file(&dict).unwrap();
+
+    assert!(first.status.success());
+    assert!(replayed.status.success());
+    assert!(!solutions(&first).is_empty());
+    assert_eq!(solutions(&replayed), solutions(&first));
+
+    assert_eq!(changed.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&changed.stderr)
+            .contains("was written for a different dictionary or parameters"),
+        "{}",
+        String::from_utf8_lossy(&changed.stderr)
+    );
+}
+
+// a run cut short after its first seed (its checkpoint written up to
+// there) and then resumed prints what the whole run does
+//
+#[test]
+fn grid_runs_resume() {
+    let checkpoint = temp_path("grid-checkpoint");
+    let dict = temp_path("grid-dict.txt");
+    let args = [
+        "--resume",
+        checkpoint.to_str().unwrap(),
+        "--grid",
+        "--len",
+        "3",
+        "--count",
+        "3",
+        dict.to_str().unwrap(),
+    ];
+
+    let _ = fs::remove_file(&checkpoint);
+    fs::write(
+        &dict,
+        "abc\ndef\nghi\nadg\nbeh\ncfi\njkl\nmno\npqr\njmp\nknq\nlor\n",
+    )
+    .unwrap();
+    let whole = fivewords(&args);
+
+    let text = fs::read_to_string(&checkpoint).unwrap();
+    let second = text.match_indices("\nseed ").nth(1).unwrap().0;
+    fs::write(&checkpoint, &text[..second + 1]).unwrap();
+    let resumed = fivewords(&args);
+
+    fs::remove_file(&checkpoint).unwrap();
+    fs::remove_file(&dict).unwrap();
+
+    assert!(whole.status.success());
+    assert!(
+        resumed.status.success(),
+        "{}",
+        String::from_utf8_lossy(&resumed.stderr)
+    );
+
+    let squares = |output: &Output| {
+        let mut squares: Vec<String> = solutions(output)
+            .join("\n")
+            .split("\n\n")
+            .map(str::to_string)
+            .collect();
+
+        squares.sort();
+        squares
+    };
+
+    assert_eq!(squares(&whole).len(), 76);
+    assert_eq!(squares(&resumed), squares(&whole));
+}
@@ -0,0 +1,142 @@
+// a corpus of dictionaries, each solved with a few sets of options and
+// checked against the solutions it gave before: how many, and a hash of
+// them all (each set of words sorted, and the sets sorted), so a change
+// to the search that finds a different set, not just a different
+// number, shows up too. The cases are in tests/corpus/expected.txt, one
+// per line:
+//
+//   dict=five.txt len=5 count=4 solutions=123 hash=0123456789abcdef
+//
+// with the dictionaries named relative to tests/corpus (bar the bundled
+// words.txt).
+//
+use std::fs;
+
+use fivewords::{Dedup, SolverBuilder};
+
+struct Case {
+    line: usize,
+    dict: String,
+    builder: SolverBuilder,
+    solutions: usize,
+    hash: u64,
+}
+
+fn cases() -> Vec<Case> {
+    let text = fs::read_to_string("tests/corpus/expected.txt").unwrap();
+    let mut cases = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut dict = None;
+        let mut builder = SolverBuilder::new();
+        let (mut len, mut max_len) = (5, None);
+        let (mut solutions, mut hash) = (None, None);
+
+        for field in line.split_whitespace() {
+            let (key, value) = field
+                .split_once('=')
+                .unwrap_or_else(|| panic!("line {}: {:?} isn't key=value", i + 1, field));
+            let number = || {
+                value
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| panic!("line {}: {:?} isn't a number", i + 1, value))
+            };
+
+            match key {
+                "dict" => dict = Some(value.to_string()),
+                "len" => len = number(),
+                "max_len" => max_len = Some(number()),
+                "count" => builder = builder.set_size(number()),
+                "exclude" => builder = builder.excluded_letters(value),
+                "no_skip" => builder = builder.allow_skip(value != "true"),
+                "min_unique" => builder = builder.min_unique(number()),
+                "dedup" => builder = builder.dedup_by(value.parse::<Dedup>().unwrap()),
+                "solutions" => solutions = Some(number()),
+                "hash" => hash = Some(u64::from_str_radix(value, 16).unwrap()),
+                _ => panic!("line {}: unknown key {:?}", i + 1, key),
+            }
+        }
+
+        cases.push(Case {
+            line: i + 1,
+            dict: dict.unwrap_or_else(|| panic!("line {}: no dict", i + 1)),
+            builder: builder.word_len_range(len..=max_len.unwrap_or(len)),
+            solutions: solutions.unwrap_or_else(|| panic!("line {}: no solutions", i + 1)),
+            hash: hash.unwrap_or_else(|| panic!("line {}: no hash", i + 1)),
+        });
+    }
+    cases
+}
+
+// FNV-1a, as Solution::id uses, so the hash is the same on every
+// platform and toolchain
+//
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100_0000_01b3)
+    })
+}
+
+fn solve(case: &Case) -> (usize, u64) {
+    let path = match case.dict.as_str() {
+        "words.txt" => "words.txt".to_string(),
+        dict => format!("tests/corpus/{}", dict),
+    };
+    let dict = fs::read_to_string(&path).unwrap();
+
+    let mut sets: Vec<String> = case
+        .builder
+        .clone()
+        .build()
+        .unwrap()
+        .solve(&dict)
+        .iter()
+        .map(|solution| {
+            let mut words: Vec<String> = solution.words().iter().map(|w| w.to_string()).collect();
+            words.sort();
+            words.join(" ")
+        })
+        .collect();
+
+    sets.sort();
+
+    let hash = sets.iter().fold(0xcbf2_9ce4_8422_2325, |hash, set| {
+        fnv1a(fnv1a(hash, set.as_bytes()), b"\n")
+    });
+
+    (sets.len(), hash)
+}
+
+#[test]
+fn corpus_solutions_are_unchanged() {
+    let cases = cases();
+    assert!(!cases.is_empty());
+
+    // (every case is checked before failing, so one run shows all
+    // that changed)
+    //
+    let mut changed = Vec::new();
+
+    for case in &cases {
+        let (solutions, hash) = solve(case);
+
+        if (solutions, hash) != (case.solutions, case.hash) {
+            changed.push(format!(
+                "line {} ({}): {} solutions, hash {:016x} (expected {}, {:016x})",
+                case.line, case.dict, solutions, hash, case.solutions, case.hash
+            ));
+        }
+    }
+
+    assert!(
+        changed.is_empty(),
+        "the corpus solutions changed:\n{}",
+        changed.join("\n")
+    );
+}
@@ -0,0 +1,104 @@
+// the two dedup policies on a dictionary full of anagrams: one
+// solution per set of letter sets, or one per way of spelling it
+//
+use fivewords::{Anagram, Dedup, Error, SolverBuilder};
+
+const DICT: &str = "abcde\nbaced\nedcba\nfghij\njihgf\nklmno\nKLMNO\n";
+
+fn solve(dedup: Dedup) -> Vec<String> {
+    solve_with(builder().dedup_by(dedup))
+}
+
+fn builder() -> SolverBuilder {
+    SolverBuilder::new()
+        .word_len(5)
+        .set_size(3)
+        .allowed_letters("abcdefghijklmno")
+}
+
+fn solve_with(builder: SolverBuilder) -> Vec<String> {
+    solve_dict(builder, DICT)
+}
+
+fn solve_dict(builder: SolverBuilder, dict: &str) -> Vec<String> {
+    let mut solutions: Vec<String> = builder
+        .build()
+        .unwrap()
+        .solve(dict)
+        .iter()
+        .map(|solution| {
+            let mut words: Vec<String> = solution.words().iter().map(|w| w.to_string()).collect();
+            words.sort();
+            words.join(" ")
+        })
+        .collect();
+
+    solutions.sort();
+    solutions
+}
+
+#[test]
+fn bitmask_collapses_anagrams() {
+    assert_eq!(solve(Dedup::Bitmask), ["abcde fghij klmno"]);
+}
+
+// three spellings of the first set, two of the second, and "KLMNO"
+// is just "klmno" again
+//
+#[test]
+fn original_keeps_every_spelling() {
+    let solutions = solve(Dedup::Original);
+
+    assert_eq!(solutions.len(), 3 * 2);
+    assert!(solutions.contains(&"baced jihgf klmno".to_string()));
+}
+
+// capped at two spellings a slot, the first set keeps its first two
+// alphabetically ("abcde" and "baced", not "edcba")
+//
+#[test]
+fn max_spellings_caps_each_slot() {
+    let solutions = solve_with(builder().dedup_by(Dedup::Original).max_spellings(2));
+
+    assert_eq!(solutions.len(), 2 * 2);
+    assert!(solutions.iter().all(|solution| !solution.contains("edcba")));
+}
+
+#[test]
+fn one_spelling_a_slot_is_one_solution() {
+    let solutions = solve_with(builder().dedup_by(Dedup::Original).max_spellings(1));
+
+    assert_eq!(solutions, ["abcde fghij klmno"]);
+}
+
+#[test]
+fn zero_spellings_is_an_error() {
+    assert_eq!(
+        builder().max_spellings(0).build().err(),
+        Some(Error::ZeroSpellings)
+    );
+}
+
+// the same dictionary listed backwards, so its first spellings are the
+// alphabetically last
+//
+fn reversed() -> String {
+    DICT.lines()
+        .rev()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[test]
+fn first_keeps_the_dictionary_s_spelling() {
+    assert_eq!(solve_dict(builder(), &reversed()), ["edcba jihgf klmno"]);
+}
+
+#[test]
+fn alpha_keeps_the_alphabetically_first_spelling() {
+    let builder = builder().select_anagram(Anagram::Alpha);
+
+    for dict in [DICT, &reversed()] {
+        assert_eq!(solve_dict(builder.clone(), dict), ["abcde fghij klmno"]);
+    }
+}
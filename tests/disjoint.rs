@@ -0,0 +1,58 @@
+// letters solutions must be disjoint from: the same word sets as
+// excluding them, but still counted (and reported) as unused letters
+//
+use fivewords::{Error, Solution, SolverBuilder};
+
+fn word_sets(solutions: &[Solution]) -> Vec<Vec<String>> {
+    let mut sets: Vec<Vec<String>> = solutions
+        .iter()
+        .map(|solution| {
+            let mut words: Vec<String> = solution.words().iter().map(|w| w.to_string()).collect();
+            words.sort();
+            words
+        })
+        .collect();
+
+    sets.sort();
+    sets
+}
+
+#[test]
+fn same_word_sets_as_excluding() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let solve = |builder: SolverBuilder| builder.build().unwrap().solve(&dict);
+
+    let disjoint = solve(SolverBuilder::new().disjoint_from("q"));
+    let excluded = solve(SolverBuilder::new().excluded_letters("q"));
+
+    assert!(!disjoint.is_empty());
+    assert_eq!(word_sets(&disjoint), word_sets(&excluded));
+
+    // the one spare letter is always q, where excluding it leaves none
+    //
+    for (disjoint, excluded) in disjoint.iter().zip(&excluded) {
+        assert_eq!(
+            disjoint
+                .unused()
+                .iter()
+                .map(|z| z.chr())
+                .collect::<String>(),
+            "q"
+        );
+        assert!(excluded.unused().is_empty());
+    }
+}
+
+#[test]
+fn disjoint_letters_use_up_the_spare_letters() {
+    let build = |letters| SolverBuilder::new().disjoint_from(letters).build();
+
+    assert!(build("q").is_ok());
+    assert_eq!(
+        build("qx").err(),
+        Some(Error::TooFewLetters {
+            needed: 25,
+            available: 24
+        })
+    );
+}
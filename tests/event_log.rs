@@ -0,0 +1,91 @@
+// an event log on a single thread traces the search step by step, its
+// solution events matching the solutions the solve returns
+//
+use std::sync::{Arc, Mutex};
+
+use fivewords::{Backend, Event, SolverBuilder};
+
+const DICT: &str = "abcde\nfghij\nklmno\nabfgk\nchlmd\npqrst\n";
+
+fn traced(backend: Backend) -> (Vec<Event>, Vec<String>) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let log = events.clone();
+
+    let solutions = SolverBuilder::new()
+        .word_len(5)
+        .set_size(3)
+        .allowed_letters("abcdefghijklmnop")
+        .backend(backend)
+        .threads(1)
+        .event_log(move |event| log.lock().unwrap().push(event.clone()))
+        .build()
+        .unwrap()
+        .solve(DICT)
+        .iter()
+        .map(|solution| solution.to_string())
+        .collect();
+
+    let events = events.lock().unwrap().clone();
+
+    (events, solutions)
+}
+
+fn solutions(events: &[Event]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Solution { words } => Some(
+                words
+                    .iter()
+                    .map(|word| word.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn solution_events_match_the_solutions() {
+    let (events, found) = traced(Backend::Recursive);
+
+    assert!(!found.is_empty());
+    assert_eq!(solutions(&events), found);
+}
+
+#[test]
+fn every_branch_starts_with_its_seed() {
+    let (events, _) = traced(Backend::Recursive);
+
+    assert!(matches!(events[0], Event::Seed { seed: Some(_) }));
+}
+
+// each word tried contains the letter its branch is filling
+//
+#[test]
+fn tries_fill_the_letter_entered() {
+    let (events, _) = traced(Backend::Recursive);
+    let mut filling = Vec::new();
+
+    for event in &events {
+        match event {
+            Event::Enter { depth, letter, .. } => {
+                filling.truncate(*depth);
+                filling.push(*letter);
+            }
+            Event::Try { depth, word } => {
+                assert!(word.iter().any(|z| z == filling[*depth]));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn both_backends_log_the_same_steps() {
+    let (recursive, _) = traced(Backend::Recursive);
+    let (iterative, _) = traced(Backend::Iterative);
+
+    assert_eq!(format!("{:?}", recursive), format!("{:?}", iterative));
+}
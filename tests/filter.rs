@@ -0,0 +1,51 @@
+// a predicate on the builder, keeping only the solutions it accepts
+//
+use fivewords::{Solution, SolverBuilder};
+
+fn starts_with_f(solution: &Solution) -> bool {
+    solution
+        .words()
+        .iter()
+        .any(|word| word.iter().next().is_some_and(|z| z.chr() == 'f'))
+}
+
+#[test]
+fn filter_keeps_only_accepted_solutions() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    let all = SolverBuilder::new().build().unwrap().solve(&dict);
+    let kept = SolverBuilder::new()
+        .filter(starts_with_f)
+        .build()
+        .unwrap()
+        .solve(&dict);
+
+    let expected: Vec<String> = all
+        .iter()
+        .filter(|solution| starts_with_f(solution))
+        .map(Solution::to_string)
+        .collect();
+
+    assert!(!expected.is_empty() && expected.len() < all.len());
+    assert_eq!(
+        kept.iter().map(Solution::to_string).collect::<Vec<_>>(),
+        expected
+    );
+}
+
+// the limit counts kept solutions, so it still fills up
+//
+#[test]
+fn limit_counts_filtered_solutions() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    let kept = SolverBuilder::new()
+        .filter(starts_with_f)
+        .limit(3)
+        .build()
+        .unwrap()
+        .solve(&dict);
+
+    assert_eq!(kept.len(), 3);
+    assert!(kept.iter().all(starts_with_f));
+}
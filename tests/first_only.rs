@@ -0,0 +1,52 @@
+// --first-only is a limit of one that nothing else may change: with
+// --limit too, in either order, it's an error rather than whichever
+// came last
+//
+use std::process::{Command, Output};
+
+fn fivewords(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(args)
+        .args(["--max-depth-trace", "0", "--no-warn"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn first_only_prints_one_solution() {
+    let output = fivewords(&["--first-only"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        stdout
+            .lines()
+            .filter(|line| !line.starts_with("Elapsed"))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn first_only_then_limit_is_an_error() {
+    let output = fivewords(&["--first-only", "--limit", "5"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: --first-only can't be combined with --limit\n"
+    );
+}
+
+#[test]
+fn limit_then_first_only_is_an_error() {
+    let output = fivewords(&["--limit", "5", "--first-only"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: --first-only can't be combined with --limit\n"
+    );
+}
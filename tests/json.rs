@@ -0,0 +1,121 @@
+// the JSON output (and the --event-log) escapes what it writes as
+// strings, so --tokens with quotes and backslashes in them still make
+// valid JSON
+//
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn fivewords(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(args)
+        .args(["--max-depth-trace", "0", "--no-warn"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{:?}", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn temp_file(name: &str, text: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("fivewords-{}-{}", std::process::id(), name));
+
+    fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn tokens_are_escaped() {
+    let tokens = temp_file("tokens.txt", "k\"a\nb\\i\nzu\nmo\nre\n");
+    let dict = temp_file("dict.txt", "k\"azu\nb\\imo\nrezu\nk\"amo\nb\\ire\n");
+    let events = temp_file("events.jsonl", "");
+
+    let json = fivewords(&[
+        "--tokens",
+        tokens.to_str().unwrap(),
+        "--len",
+        "2",
+        "--count",
+        "2",
+        "--format",
+        "json",
+        "--threads",
+        "1",
+        "--event-log",
+        events.to_str().unwrap(),
+        dict.to_str().unwrap(),
+    ]);
+    let log = fs::read_to_string(&events).unwrap();
+
+    for path in [tokens, dict, events] {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert!(
+        json.contains(r#""words": ["k\"azu", "b\\imo"], "unused": ["re"]"#),
+        "{}",
+        json
+    );
+    assert_eq!(json.matches("\"id\"").count(), 5);
+    assert!(!json.contains(r#"["k"a"#) && !json.contains(r#""b\i"#));
+
+    assert!(
+        log.contains(r#"{"event": "seed", "seed": "k\"a"}"#),
+        "{}",
+        log
+    );
+    assert!(
+        log.contains(r#"{"event": "try", "depth": 0, "word": "b\\imo"}"#),
+        "{}",
+        log
+    );
+}
+
+// the params hold every constraint on the run, the whole length range
+// included
+//
+#[test]
+fn params_describe_the_run() {
+    let json = fivewords(&[
+        "--format",
+        "json",
+        "--len-range",
+        "4..6",
+        "--count",
+        "3",
+        "--must-include",
+        "Fjord",
+        "--exclude",
+        "q",
+        "--allowed",
+        "abcdefghijklmnopqrstuvwxy",
+        "--require-disjoint-from",
+        "x",
+        "--limit",
+        "2",
+        "--seed-order",
+        "size-desc",
+        "--dedup-by",
+        "original",
+    ]);
+    let params = &json[json.find("\"params\"").unwrap()..json.find("\"solutions\"").unwrap()];
+
+    for field in [
+        "\"len\": 4,",
+        "\"max_len\": 6,",
+        "\"count\": 3,",
+        "\"skip\": true,",
+        "\"allowed\": \"abcdefghijklmnopqrstuvwxy\",",
+        "\"excluded\": \"q\",",
+        "\"disjoint\": \"x\",",
+        "\"must_include\": \"fjord\",",
+        "\"dedup\": \"original\",",
+        "\"limit\": 2,",
+        "\"seed_order\": \"size-desc\",",
+        "\"dictionary_hash\": \"",
+    ] {
+        assert!(params.contains(field), "no {} in {}", field, params);
+    }
+    assert!(json.starts_with("{\n  \"version\": 2,\n"));
+    assert_eq!(json.matches("\"id\"").count(), 2);
+}
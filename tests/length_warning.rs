@@ -0,0 +1,33 @@
+// a word length with almost no usable words, next to the dictionary's
+// main length, gets a warning; the bundled dictionary at its default
+// length gets none
+//
+use std::process::Command;
+
+fn stderr(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(args)
+        .args(["--first-only", "--max-depth-trace", "0"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{:?}", args);
+    String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn the_default_run_is_quiet() {
+    assert!(!stderr(&[]).contains("warning"));
+}
+
+#[test]
+fn a_length_with_almost_no_words_is_warned_of() {
+    let warned = stderr(&["--len", "11", "--count", "1"]);
+
+    assert!(
+        warned.contains("warning: only 903 of the dictionary's 65905 usable words have 11 letters, against 13857 with 6"),
+        "{}",
+        warned
+    );
+    assert!(!stderr(&["--len", "11", "--count", "1", "--no-warn"]).contains("warning"));
+}
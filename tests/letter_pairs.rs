@@ -0,0 +1,36 @@
+// co-occurrence counts of the rarest letters, checked against a plain
+// count over the candidates
+//
+use fivewords::{SolverBuilder, ZChar};
+
+#[test]
+fn counts_match_the_candidates() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let solver = SolverBuilder::new().build().unwrap();
+    let prepared = solver.prepare(&dict);
+
+    let pairs = solver.letter_pairs(&prepared, 6);
+    let candidates = solver.candidates(&prepared);
+
+    let holding = |a: ZChar, b: ZChar| {
+        candidates
+            .iter()
+            .filter(|word| word.iter().any(|z| z == a) && word.iter().any(|z| z == b))
+            .count()
+    };
+
+    assert_eq!(pairs.letters().len(), 6);
+    for &a in pairs.letters() {
+        for &b in pairs.letters() {
+            assert_eq!(pairs.count(a, b), Some(holding(a, b)));
+        }
+    }
+
+    for (a, b) in pairs.never_together() {
+        assert_eq!(holding(a, b), 0);
+    }
+    assert_eq!(
+        pairs.count(ZChar::try_from('e').unwrap(), ZChar::try_from('q').unwrap()),
+        None
+    );
+}
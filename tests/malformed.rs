@@ -0,0 +1,48 @@
+// the lines a strict run objects to: each with its line number, what's
+// wrong with it, and where in the word
+//
+use fivewords::{Problem, SolverBuilder};
+
+const DICT: &str = "crane\n\ncoöp\nFloor\nox\nabcdef\n";
+
+#[test]
+fn every_malformed_line_is_found() {
+    let solver = SolverBuilder::new().build().unwrap();
+    let malformed: Vec<_> = solver.malformed(DICT).collect();
+
+    let lines: Vec<_> = malformed.iter().map(|m| m.line).collect();
+    assert_eq!(lines, [3, 4, 5, 6]);
+
+    assert_eq!(malformed[0].problem, Problem::NotALetter('ö'));
+    assert_eq!(malformed[0].position, Some(3));
+    assert_eq!(malformed[1].problem, Problem::RepeatedLetter('o'));
+    assert_eq!(malformed[1].position, Some(4));
+    assert_eq!(malformed[2].problem, Problem::WrongLength(2));
+    assert_eq!(malformed[2].position, None);
+
+    assert_eq!(solver.validate(DICT), Err(malformed[0].clone()));
+}
+
+#[test]
+fn messages_say_what_and_where() {
+    let solver = SolverBuilder::new().word_len_range(4..=5).build().unwrap();
+    let messages: Vec<_> = solver.malformed(DICT).map(|m| m.to_string()).collect();
+
+    assert_eq!(
+        messages,
+        [
+            "line 3: \"coöp\" has 'ö' at position 3, which isn't a plain letter",
+            "line 4: \"Floor\" repeats 'o' at position 4",
+            "line 5: \"ox\" has 2 letters, not 4 to 5",
+            "line 6: \"abcdef\" has 6 letters, not 4 to 5",
+        ]
+    );
+}
+
+#[test]
+fn a_clean_dictionary_validates() {
+    let solver = SolverBuilder::new().build().unwrap();
+
+    assert_eq!(solver.validate("crane\n\nfghij\n"), Ok(()));
+    assert_eq!(solver.malformed("crane\n").count(), 0);
+}
@@ -0,0 +1,101 @@
+// a search that must include a word finds just the solutions with it
+// in them, the same (and in the same order) as picking them out of the
+// full search
+//
+use fivewords::{Dedup, Error, Problem, Solution, SolverBuilder};
+
+fn solutions(builder: SolverBuilder, dict: &str) -> Vec<Solution> {
+    builder.build().unwrap().solve(dict)
+}
+
+fn check(builder: SolverBuilder, dict: &str, word: &str) {
+    let letters = |word: &str| {
+        let mut letters: Vec<char> = word.to_lowercase().chars().collect();
+        letters.sort();
+        letters
+    };
+
+    let expected: Vec<String> = solutions(builder.clone(), dict)
+        .iter()
+        .filter(|solution| {
+            solution
+                .words()
+                .iter()
+                .any(|w| letters(&w.to_string()) == letters(word))
+        })
+        .map(Solution::to_string)
+        .collect();
+    let found: Vec<String> = solutions(builder.must_include(word), dict)
+        .iter()
+        .map(Solution::to_string)
+        .collect();
+
+    assert!(!expected.is_empty(), "no solutions with {:?}", word);
+    assert_eq!(found, expected, "with {:?}", word);
+}
+
+#[test]
+fn only_solutions_with_the_word_are_found() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    for word in ["fjord", "glyph", "waltz", "nymph"] {
+        check(SolverBuilder::new(), &dict, word);
+    }
+    check(SolverBuilder::new().threads(3), &dict, "waltz");
+    check(
+        SolverBuilder::new().dedup_by(Dedup::Original),
+        &dict,
+        "glyph",
+    );
+}
+
+#[test]
+fn words_of_several_lengths_and_shared_letters() {
+    let dict = std::fs::read_to_string("tests/corpus/mixed.txt").unwrap();
+    let builder = SolverBuilder::new()
+        .word_len_range(4..=6)
+        .set_size(4)
+        .min_unique(22);
+
+    let with: Vec<Solution> = solutions(builder.clone(), &dict);
+    let word = with[0].words()[1].to_string();
+
+    check(builder, &dict, &word);
+
+    let dict = std::fs::read_to_string("tests/corpus/four.txt").unwrap();
+    let builder = SolverBuilder::new().word_len(4).set_size(6);
+
+    let with: Vec<Solution> = solutions(builder.clone(), &dict);
+    let word = with[with.len() / 2].words()[3].to_string();
+
+    check(builder, &dict, &word);
+}
+
+#[test]
+fn a_word_absent_from_the_dictionary_finds_nothing() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    assert!(solutions(SolverBuilder::new().must_include("abcde"), &dict).is_empty());
+}
+
+#[test]
+fn an_unusable_word_is_an_error() {
+    let build = |word: &str| SolverBuilder::new().must_include(word).build().err();
+
+    assert_eq!(
+        build("floor"),
+        Some(Error::UnusableWord(Problem::RepeatedLetter('o')))
+    );
+    assert_eq!(
+        build("fjor"),
+        Some(Error::UnusableWord(Problem::WrongLength(4)))
+    );
+    assert_eq!(
+        SolverBuilder::new()
+            .excluded_letters("j")
+            .must_include("fjord")
+            .build()
+            .err(),
+        Some(Error::RequiredUnavailable('j'))
+    );
+}
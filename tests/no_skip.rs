@@ -0,0 +1,36 @@
+// without skips, a search of words of several lengths still finds the
+// sets that use every letter: just the ones the full search finds with
+// none left unused
+//
+use fivewords::{Solution, SolverBuilder};
+
+const DICT: &str = "abcdef\nghijkl\nmnopqr\nstuv\nwxyz\nstuw\nvxyz\nwxy\nabcd\nefghij\n";
+
+fn solve(allow_skip: bool) -> Vec<Solution> {
+    SolverBuilder::new()
+        .word_len_range(3..=6)
+        .set_size(5)
+        .allow_skip(allow_skip)
+        .build()
+        .unwrap()
+        .solve(DICT)
+}
+
+#[test]
+fn no_skip_finds_the_sets_using_every_letter() {
+    let all = solve(true);
+    let strict = solve(false);
+
+    let full: Vec<String> = all
+        .iter()
+        .filter(|solution| solution.unused().is_empty())
+        .map(Solution::to_string)
+        .collect();
+
+    assert!(all.len() > full.len());
+    assert_eq!(full.len(), 2);
+    assert_eq!(
+        strict.iter().map(Solution::to_string).collect::<Vec<_>>(),
+        full
+    );
+}
@@ -0,0 +1,24 @@
+// sorting by unused letter lists the same solutions, grouped by the
+// letter they leave out and alphabetical within each group
+//
+use fivewords::{Order, Solution, SolverBuilder};
+
+#[test]
+fn unused_order_groups_by_unused_letter() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    let solve = |order| {
+        SolverBuilder::new()
+            .order(order)
+            .build()
+            .unwrap()
+            .solve(&dict)
+    };
+    let key = |solution: &Solution| (solution.unused()[0].chr(), solution.to_string());
+
+    let sorted: Vec<_> = solve(Order::Unused).iter().map(key).collect();
+    let mut expected: Vec<_> = solve(Order::Found).iter().map(key).collect();
+
+    expected.sort();
+    assert_eq!(sorted, expected);
+}
@@ -0,0 +1,102 @@
+// --output-limit-bytes caps what every kind of run prints, not just the
+// plain solution list, and says so on stderr when it cut the output
+// short
+//
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn fivewords(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(args)
+        .args(["--max-depth-trace", "0", "--no-warn"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{:?}", args);
+    output
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("fivewords-{}-{}", std::process::id(), name))
+}
+
+fn assert_capped(output: &Output, limit: usize) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(
+        output.stdout.len() <= limit,
+        "{} bytes",
+        output.stdout.len()
+    );
+    assert!(!output.stdout.is_empty());
+    assert!(
+        stderr.contains("truncated: the output stopped at about"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn resume_is_capped() {
+    let checkpoint = temp_path("checkpoint");
+    let resume = ["--resume", checkpoint.to_str().unwrap()];
+    let capped = [&resume[..], &["--output-limit-bytes", "100"]].concat();
+
+    let _ = fs::remove_file(&checkpoint);
+    let first = fivewords(&capped);
+
+    // (and replaying a finished run's checkpoint)
+    //
+    let _ = fs::remove_file(&checkpoint);
+    let whole = fivewords(&resume);
+    let replayed = fivewords(&capped);
+    fs::remove_file(&checkpoint).unwrap();
+
+    assert!(whole.stdout.len() > 100);
+    assert_capped(&first, 100);
+    assert_capped(&replayed, 100);
+    assert!(whole.stdout.starts_with(&replayed.stdout));
+}
+
+#[test]
+fn word_squares_are_capped() {
+    // two 3x3 squares, each of whose rows and columns are words, so
+    // four arrangements in all
+    //
+    let dict = temp_path("squares.txt");
+    fs::write(
+        &dict,
+        "abc\ndef\nghi\nadg\nbeh\ncfi\njkl\nmno\npqr\njmp\nknq\nlor\n",
+    )
+    .unwrap();
+
+    let args = [
+        "--word-square",
+        "--len",
+        "3",
+        "--count",
+        "3",
+        dict.to_str().unwrap(),
+    ];
+    let whole = fivewords(&args);
+    let capped = fivewords(&[&args[..], &["--output-limit-bytes", "30"]].concat());
+
+    fs::remove_file(&dict).unwrap();
+
+    assert!(whole.stdout.len() > 30);
+    assert_capped(&capped, 30);
+    assert!(whole.stdout.starts_with(&capped.stdout));
+}
+
+#[test]
+fn reports_are_rejected() {
+    let output = Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(["--summary", "--output-limit-bytes", "100"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("error: --output-limit-bytes only limits solutions"));
+}
@@ -0,0 +1,73 @@
+// a solve reports its progress once as it starts, then once per
+// branch, ending with every branch done and every solution counted
+//
+use std::sync::{Arc, Mutex};
+
+use fivewords::{Progress, SolverBuilder};
+
+fn reports(builder: SolverBuilder, dict: &str) -> (Vec<Progress>, usize) {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let log = reports.clone();
+
+    let solutions = builder
+        .on_progress(move |progress| log.lock().unwrap().push(progress))
+        .build()
+        .unwrap()
+        .solve(dict)
+        .len();
+
+    let reports = reports.lock().unwrap().clone();
+    (reports, solutions)
+}
+
+#[test]
+fn progress_counts_up_every_branch() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let (reports, solutions) = reports(SolverBuilder::new().threads(1), &dict);
+    let last = reports.last().unwrap();
+
+    assert_eq!(reports.len(), 27);
+    assert_eq!(reports[0].done, 0);
+    assert!(reports.iter().all(|progress| progress.branches == 26));
+    assert!(reports.windows(2).all(|pair| pair[0].done < pair[1].done));
+    assert_eq!((last.done, last.solutions), (26, solutions));
+}
+
+// (on several threads the reports may arrive out of order, but each
+// branch is still counted once)
+//
+#[test]
+fn every_thread_reports_its_branches() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let (reports, _) = reports(SolverBuilder::new().threads(4), &dict);
+
+    let mut done: Vec<usize> = reports.iter().map(|progress| progress.done).collect();
+    done.sort();
+
+    assert_eq!(done, (0..=26).collect::<Vec<_>>());
+}
+
+#[test]
+fn a_search_without_seeds_is_one_branch() {
+    let (reports, solutions) = reports(
+        SolverBuilder::new().excluded_letters("q"),
+        "abcde\nfghij\nklmno\nprstu\nvwxyz\n",
+    );
+
+    assert_eq!(solutions, 1);
+    assert_eq!(
+        reports,
+        [
+            Progress {
+                done: 0,
+                branches: 1,
+                solutions: 0,
+            },
+            Progress {
+                done: 1,
+                branches: 1,
+                solutions: 1,
+            },
+        ]
+    );
+}
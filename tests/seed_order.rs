@@ -0,0 +1,71 @@
+// the seed order changes which branch the search starts first, but
+// never which solutions a solve returns
+//
+use std::sync::{Arc, Mutex};
+
+use fivewords::{Event, PreparedDict, SeedOrder, Solution, SolverBuilder, ZChar};
+
+fn seeds(dict: &str, order: SeedOrder) -> Vec<ZChar> {
+    let seeds = Arc::new(Mutex::new(Vec::new()));
+    let log = seeds.clone();
+
+    SolverBuilder::new()
+        .seed_order(order)
+        .threads(1)
+        .event_log(move |event| {
+            if let Event::Seed { seed: Some(seed) } = event {
+                log.lock().unwrap().push(*seed);
+            }
+        })
+        .build()
+        .unwrap()
+        .solve(dict);
+
+    let seeds = seeds.lock().unwrap().clone();
+    seeds
+}
+
+#[test]
+fn size_desc_starts_the_biggest_branches_first() {
+    let dict = std::fs::read_to_string("tests/corpus/five.txt").unwrap();
+    let solver = SolverBuilder::new().build().unwrap();
+    let candidates = solver.candidates(&PreparedDict::new(&dict));
+
+    // a branch's size: the candidates without the letter it skips
+    //
+    let size = |seed: &ZChar| {
+        candidates
+            .iter()
+            .filter(|word| !word.iter().any(|z| z == *seed))
+            .count()
+    };
+
+    let desc: Vec<usize> = seeds(&dict, SeedOrder::SizeDesc).iter().map(size).collect();
+    let asc: Vec<usize> = seeds(&dict, SeedOrder::SizeAsc).iter().map(size).collect();
+
+    assert_eq!(desc.len(), 26);
+    assert!(desc.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!(asc.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+#[test]
+fn every_order_finds_the_same_solutions() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    let solve = |order: SeedOrder| -> Vec<String> {
+        SolverBuilder::new()
+            .seed_order(order)
+            .build()
+            .unwrap()
+            .solve(&dict)
+            .iter()
+            .map(Solution::to_string)
+            .collect()
+    };
+
+    let natural = solve(SeedOrder::Natural);
+
+    assert_eq!(natural.len(), 538);
+    assert_eq!(solve(SeedOrder::SizeDesc), natural);
+    assert_eq!(solve(SeedOrder::SizeAsc), natural);
+}
@@ -0,0 +1,61 @@
+// the index's invariants hold for the bundled dictionary, with the
+// default settings and with a smaller, restricted alphabet
+//
+use std::process::Command;
+
+use fivewords::SolverBuilder;
+
+fn assert_passes(builder: SolverBuilder) {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let solver = builder.build().unwrap();
+
+    let checks = solver.self_test(&solver.prepare(&dict));
+
+    assert!(!checks.is_empty());
+    for check in checks {
+        assert_eq!(check.result, Ok(()), "{}", check.name);
+    }
+}
+
+#[test]
+fn default_index_passes() {
+    assert_passes(SolverBuilder::new());
+}
+
+#[test]
+fn restricted_index_passes() {
+    assert_passes(
+        SolverBuilder::new()
+            .word_len(4)
+            .set_size(5)
+            .excluded_letters("qxz"),
+    );
+}
+
+// the `selftest` subcommand reports the same checks as --self-test
+//
+#[test]
+fn selftest_subcommand_runs_every_check() {
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_fivewords"))
+            .args(args)
+            .arg("tests/corpus/five.txt")
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "{:?}", args);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let report = run(&["selftest"]);
+
+    assert!(report.starts_with("self-test: tests/corpus/five.txt"));
+    assert_eq!(
+        report
+            .lines()
+            .filter(|line| line.starts_with("  pass"))
+            .count(),
+        5
+    );
+    assert_eq!(report, run(&["--self-test"]));
+}
@@ -0,0 +1,100 @@
+// a fixed number of threads deals the seeds out the same way every
+// time, and limits the solutions in seed order, so a limited search
+// keeps the same solutions whatever the number of threads
+//
+use fivewords::{Solution, SolverBuilder};
+
+fn limited(threads: usize, limit: usize) -> Vec<String> {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    SolverBuilder::new()
+        .threads(threads)
+        .limit(limit)
+        .build()
+        .unwrap()
+        .solve(&dict)
+        .iter()
+        .map(Solution::to_string)
+        .collect()
+}
+
+#[test]
+fn limit_keeps_the_first_solutions_in_seed_order() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    let all: Vec<String> = SolverBuilder::new()
+        .threads(1)
+        .build()
+        .unwrap()
+        .solve(&dict)
+        .iter()
+        .map(Solution::to_string)
+        .collect();
+
+    for threads in [1, 2, 3, 7] {
+        assert_eq!(limited(threads, 40), all[..40]);
+    }
+}
+
+#[test]
+fn unlimited_finds_every_solution() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+
+    let expected = SolverBuilder::new().build().unwrap().solve(&dict);
+    let found = SolverBuilder::new()
+        .threads(4)
+        .build()
+        .unwrap()
+        .solve(&dict);
+
+    let strings = |solutions: Vec<Solution>| -> Vec<String> {
+        solutions.iter().map(Solution::to_string).collect()
+    };
+    assert_eq!(strings(found), strings(expected));
+}
+
+#[test]
+fn zero_threads_is_an_error() {
+    assert_eq!(
+        SolverBuilder::new().threads(0).build().err(),
+        Some(fivewords::Error::ZeroThreads)
+    );
+}
+
+#[test]
+fn a_shared_pool_finds_the_same_solutions() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let pool = std::sync::Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+
+    let strings = |solutions: Vec<Solution>| -> Vec<String> {
+        solutions.iter().map(Solution::to_string).collect()
+    };
+
+    // on rayon's scheduling, and on a fixed partition of the seeds
+    //
+    let pooled = SolverBuilder::new().thread_pool(pool.clone());
+    assert_eq!(
+        strings(pooled.clone().build().unwrap().solve(&dict)),
+        strings(SolverBuilder::new().build().unwrap().solve(&dict))
+    );
+
+    for _ in 0..2 {
+        assert_eq!(
+            strings(
+                pooled
+                    .clone()
+                    .threads(3)
+                    .limit(40)
+                    .build()
+                    .unwrap()
+                    .solve(&dict)
+            ),
+            limited(3, 40)
+        );
+    }
+}
@@ -0,0 +1,34 @@
+// a time budget stops the search where it is, and says so; one that's
+// long enough changes nothing
+//
+use std::time::Duration;
+
+use fivewords::SolverBuilder;
+
+#[test]
+fn exhausted_budget_stops_early() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let solver = SolverBuilder::new()
+        .time_budget(Duration::ZERO)
+        .build()
+        .unwrap();
+
+    let (solutions, stats) = solver.solve_prepared_stats(&solver.prepare(&dict));
+
+    assert!(stats.timed_out);
+    assert!(solutions.len() < 538);
+}
+
+#[test]
+fn ample_budget_finds_everything() {
+    let dict = std::fs::read_to_string("words.txt").unwrap();
+    let solver = SolverBuilder::new()
+        .time_budget(Duration::from_secs(3600))
+        .build()
+        .unwrap();
+
+    let (solutions, stats) = solver.solve_prepared_stats(&solver.prepare(&dict));
+
+    assert!(!stats.timed_out);
+    assert_eq!(solutions.len(), 538);
+}
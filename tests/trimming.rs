@@ -0,0 +1,109 @@
+// whitespace around dictionary words: trimmed away, with lines left
+// empty by the trimming skipped rather than counted as words
+//
+use fivewords::{NotUtf8, PreparedDict, Problem, SolverBuilder};
+
+fn words(dict: &str) -> Vec<String> {
+    PreparedDict::new(dict)
+        .words()
+        .map(|word| word.to_string())
+        .collect()
+}
+
+#[test]
+fn crlf_line_is_accepted() {
+    assert_eq!(words("abcde\r\n"), ["abcde"]);
+    assert_eq!(words("abcde\r\nfghij\r\n"), ["abcde", "fghij"]);
+}
+
+#[test]
+fn padded_line_is_accepted() {
+    assert_eq!(words("  abcde  "), ["abcde"]);
+    assert_eq!(words("\tabcde \n"), ["abcde"]);
+}
+
+#[test]
+fn empty_line_is_skipped() {
+    assert!(words("").is_empty());
+    assert_eq!(words("\nabcde\n\n"), ["abcde"]);
+}
+
+#[test]
+fn whitespace_line_is_skipped() {
+    assert!(words("   ").is_empty());
+    assert_eq!(words("   \nabcde\n \r\n"), ["abcde"]);
+}
+
+// trimmed words have the target length, so they can make solutions
+//
+#[test]
+fn trimmed_words_solve() {
+    let solver = SolverBuilder::new()
+        .word_len(5)
+        .set_size(2)
+        .allowed_letters("abcdefghij")
+        .build()
+        .unwrap();
+
+    let solutions = solver.solve("abcde\r\n  fghij  \n   \n\n");
+
+    assert_eq!(solutions.len(), 1);
+}
+
+// reading a line at a time trims and skips just the same
+//
+#[test]
+fn reader_matches_text() {
+    let dict = "abcde\r\n  fghij \n\n   \nfloor\nklmno";
+    let read: Vec<String> = PreparedDict::from_reader(dict.as_bytes())
+        .unwrap()
+        .words()
+        .map(|word| word.to_string())
+        .collect();
+
+    assert_eq!(read, words(dict));
+    assert_eq!(read, ["abcde", "fghij", "klmno"]);
+}
+
+// parsing a single word trims it the same way, whatever its length
+//
+#[test]
+fn parse_word_trims_and_reads_any_length() {
+    let solver = SolverBuilder::new().build().unwrap();
+
+    assert_eq!(solver.parse_word("  Crane\r").unwrap().to_string(), "crane");
+    assert_eq!(solver.parse_word("ox").unwrap().to_string(), "ox");
+    assert_eq!(
+        solver.parse_word("floor").unwrap_err(),
+        Problem::RepeatedLetter('o')
+    );
+    assert_eq!(
+        solver.parse_word("can't").unwrap_err(),
+        Problem::NotALetter('\'')
+    );
+}
+
+// read from raw bytes, a dictionary comes out as it does from its
+// text, and a line that isn't UTF-8 is pointed out
+//
+#[test]
+fn bytes_read_like_text() {
+    let dict = "  abcde\r\n\nfghij\nklmno";
+    let read: Vec<String> = PreparedDict::from_bytes(dict.as_bytes())
+        .unwrap()
+        .words()
+        .map(|word| word.to_string())
+        .collect();
+
+    assert_eq!(read, words(dict));
+}
+
+#[test]
+fn bytes_that_arent_utf8_are_an_error() {
+    let dict = b"abcde\nfgh\xffij\nklmno\n";
+
+    assert_eq!(
+        PreparedDict::from_bytes(dict).err(),
+        Some(NotUtf8 { line: 2 })
+    );
+}
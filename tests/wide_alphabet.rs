@@ -0,0 +1,91 @@
+// an alphabet of more than 32 symbols (letters carrying on past 'z')
+// is searched with 64-bit masks, and solves just like the 26 letters:
+// here 40 symbols, as eight rows of five, with the rows the only words
+// that share nothing
+//
+use std::process::Command;
+
+use fivewords::{SolverBuilder, ZChar};
+
+const CONSONANTS: &str = "kstnhmyr";
+const VOWELS: &str = "aiueo";
+
+fn symbol(i: usize) -> char {
+    ZChar::from_ord(i).unwrap().chr()
+}
+
+#[test]
+fn forty_symbols_use_wide_masks() {
+    let alphabet: String = (0..40).map(symbol).collect();
+    let mut dict: Vec<String> = (0..8)
+        .map(|row| (0..5).map(|i| symbol(row * 5 + i)).collect())
+        .collect();
+
+    // (and a column, which overlaps every row)
+    //
+    dict.push((0..5).map(|row| symbol(row * 5)).collect());
+
+    let solver = SolverBuilder::new()
+        .allowed_letters(&alphabet)
+        .set_size(7)
+        .build()
+        .unwrap();
+    let solutions = solver.solve(&dict.join("\n"));
+
+    assert_eq!(solutions.len(), 8);
+
+    for solution in &solutions {
+        let unused: String = solution.unused().iter().map(ZChar::chr).collect();
+
+        assert!(dict[..8].contains(&unused), "{:?}", unused);
+    }
+    assert!(solutions
+        .iter()
+        .any(|solution| solution.masks().iter().any(|&mask| mask >> 32 != 0)));
+}
+
+#[test]
+fn forty_tokens_solve() {
+    let path = |name: &str| {
+        std::env::temp_dir().join(format!("fivewords-{}-{}", std::process::id(), name))
+    };
+    let (tokens, dict) = (path("tokens.txt"), path("dict.txt"));
+
+    let syllables: Vec<String> = CONSONANTS
+        .chars()
+        .flat_map(|c| VOWELS.chars().map(move |v| format!("{}{}", c, v)))
+        .collect();
+    let rows: Vec<String> = syllables.chunks(5).map(|row| row.concat()).collect();
+
+    std::fs::write(&tokens, syllables.join("\n")).unwrap();
+    std::fs::write(&dict, format!("{}\nkasatanaha\n", rows.join("\n"))).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_fivewords"))
+        .args(["--tokens", tokens.to_str().unwrap()])
+        .args([
+            "--count",
+            "7",
+            "--show-unused",
+            "--max-depth-trace",
+            "0",
+            "--no-warn",
+        ])
+        .arg(&dict)
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(&tokens).unwrap();
+    std::fs::remove_file(&dict).unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let solutions: Vec<&str> = stdout
+        .lines()
+        .filter(|line| !line.starts_with("Elapsed"))
+        .collect();
+
+    assert!(output.status.success());
+    assert_eq!(solutions.len(), 8, "{}", stdout);
+    assert!(solutions.contains(
+        &"kakikukeko sasisuseso tatituteto naninuneno hahihuheho mamimumemo yayiyuyeyo [ra ri ru re ro]"
+    ));
+}
@@ -0,0 +1,57 @@
+// building words from letters, by collecting them or from a spelling
+//
+use fivewords::{Problem, ZChar, ZWord};
+
+fn letters(spelling: &str) -> impl Iterator<Item = ZChar> + '_ {
+    spelling.chars().map(|c| ZChar::try_from(c).unwrap())
+}
+
+#[test]
+fn collects_letters_in_order() {
+    let word: ZWord = letters("Crane").collect();
+
+    assert_eq!(word.len(), 5);
+    assert_eq!(word.to_string(), "crane");
+}
+
+#[test]
+fn collects_any_length() {
+    assert!(letters("").collect::<ZWord>().is_empty());
+    assert_eq!(
+        letters("abcdefghijklmnopqrstuvwxyz")
+            .collect::<ZWord>()
+            .len(),
+        26
+    );
+}
+
+#[test]
+#[should_panic(expected = "repeats")]
+fn collecting_a_repeated_letter_panics() {
+    let _: ZWord = letters("floor").collect();
+}
+
+#[test]
+fn try_from_reports_problems() {
+    assert_eq!(ZWord::try_from("fjord").unwrap().to_string(), "fjord");
+    assert_eq!(
+        ZWord::try_from("floor").err(),
+        Some(Problem::RepeatedLetter('o'))
+    );
+    assert_eq!(
+        ZWord::try_from("café").err(),
+        Some(Problem::NotALetter('é'))
+    );
+}
+
+#[test]
+fn debug_shows_letters_and_offsets() {
+    assert_eq!(
+        format!("{:?}", ZChar::try_from('Q').unwrap()),
+        "ZChar('q', 16)"
+    );
+    assert_eq!(
+        format!("{:?}", ZWord::try_from("crane").unwrap()),
+        r#"ZWord("crane", [2, 17, 0, 13, 4])"#
+    );
+}